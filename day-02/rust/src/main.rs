@@ -1,48 +1,78 @@
-#[derive(Debug, Copy, Clone)]
-struct Round {
-    red: u32,
-    green: u32,
-    blue: u32,
+mod report;
+
+use std::collections::BTreeMap;
+
+const DEFAULT_BAG: &str = "12 red, 13 green, 14 blue";
+
+/// A handful of cubes, keyed by color.
+#[derive(Debug, Clone, Default)]
+struct Round<'a> {
+    cubes: BTreeMap<&'a str, u32>,
+    /// Each color in the order it was first pulled
+    order: Vec<&'a str>,
 }
 
-impl Round {
-    fn parse(string: &str) -> Self {
-        let mut red = 0;
-        let mut green = 0;
-        let mut blue = 0;
+impl<'a> Round<'a> {
+    fn parse(string: &'a str) -> Self {
+        let mut round = Self::default();
 
         for pull in string.split(", ") {
-            let (count, color) = pull.split_once(' ').unwrap();
+            let (count, color) = pull.trim().split_once(' ').unwrap();
             let count = count.parse().unwrap();
-
-            match color {
-                "red" => red = count,
-                "green" => green = count,
-                "blue" => blue = count,
-                _ => unreachable!("bad input"),
+            if round.cubes.insert(color, count).is_none() {
+                round.order.push(color);
             }
         }
 
-        Self { red, green, blue }
+        round
     }
 
-    fn possible(&self) -> bool {
-        self.red <= 12 && self.green <= 13 && self.blue <= 14
+    fn count(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
     }
 
-    fn power(self) -> u32 {
-        self.red * self.green * self.blue
+    fn possible(&self, bag: &Round) -> bool {
+        self.first_excess(bag).is_none()
+    }
+
+    /// The first color pulled that the bag doesn't have enough of
+    fn first_excess(&self, bag: &Round) -> Option<(&'a str, u32)> {
+        self.order
+            .iter()
+            .map(|&color| (color, self.count(color)))
+            .find(|&(color, count)| count > bag.count(color))
+    }
+
+    /// Grow each color to at least as many cubes as `other` has
+    fn merge_max(&mut self, other: &Round<'a>) {
+        for &color in &other.order {
+            let count = other.count(color);
+            match self.cubes.get_mut(color) {
+                Some(max) => *max = count.max(*max),
+                None => {
+                    self.cubes.insert(color, count);
+                    self.order.push(color);
+                }
+            }
+        }
+    }
+
+    /// The product of how many cubes there are of each of the bag's colors,
+    /// so a color that's missing counts as 0 and colors the bag doesn't have
+    /// are left out
+    fn power(&self, bag: &Round) -> u32 {
+        bag.cubes.keys().map(|color| self.count(color)).product()
     }
 }
 
 #[derive(Debug, Clone)]
-struct Game {
+struct Game<'a> {
     id: u32,
-    rounds: Vec<Round>,
+    rounds: Vec<Round<'a>>,
 }
 
-impl Game {
-    fn parse(line: &str) -> Self {
+impl<'a> Game<'a> {
+    fn parse(line: &'a str) -> Self {
         let line = &line[5..]; // skip "Game "
         let (id, line) = line.split_once(": ").unwrap();
 
@@ -52,16 +82,18 @@ impl Game {
         Self { id, rounds }
     }
 
-    fn possible(&self) -> bool {
-        self.rounds.iter().all(|round| round.possible())
+    fn possible(&self, bag: &Round) -> bool {
+        self.rounds.iter().all(|round| round.possible(bag))
     }
 
-    fn fewest_cubes_possible(self) -> Round {
-        Round {
-            red: self.rounds.iter().map(|r| r.red).max().unwrap(),
-            green: self.rounds.iter().map(|r| r.green).max().unwrap(),
-            blue: self.rounds.iter().map(|r| r.blue).max().unwrap(),
+    fn fewest_cubes_possible(&self) -> Round<'a> {
+        let mut fewest = Round::default();
+
+        for round in &self.rounds {
+            fewest.merge_max(round);
         }
+
+        fewest
     }
 }
 
@@ -69,21 +101,48 @@ fn main() {
     // let input = include_str!("../../test_input.txt");
     let input = include_str!("../../input.txt");
 
-    let part1: u32 = input
+    let mut bag = String::from(DEFAULT_BAG);
+    let mut format = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bag" => bag = args.next().expect("--bag requires a value"),
+            "--report" => {
+                let value = args.next().expect("--report requires a value");
+                format = Some(report::Format::parse(&value));
+            }
+            _ => panic!("unknown argument: {arg}"),
+        }
+    }
+    let bag = Round::parse(&bag);
+
+    if let Some(format) = format {
+        let games: Vec<Game> = input.lines().map(Game::parse).collect();
+        let report = report::Report::new(&games, &bag);
+        print!("{}", report.render(format));
+        return;
+    }
+
+    println!("part1 = {}", part1(input, &bag));
+    println!("part2 = {}", part2(input, &bag));
+}
+
+fn part1(input: &str, bag: &Round) -> u32 {
+    input
         .lines()
         .map(Game::parse)
-        .filter(Game::possible)
+        .filter(|game| game.possible(bag))
         .map(|game| game.id)
-        .sum();
-
-    println!("part1 = {part1}");
+        .sum()
+}
 
-    let part2: u32 = input
+fn part2(input: &str, bag: &Round) -> u32 {
+    input
         .lines()
         .map(Game::parse)
-        .map(Game::fewest_cubes_possible)
-        .map(Round::power)
-        .sum();
-
-    println!("part2 = {part2}");
+        .map(|game| game.fewest_cubes_possible().power(bag))
+        .sum()
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,231 @@
+use std::fmt::Write;
+
+use crate::{Game, Round};
+
+#[derive(Debug, Copy, Clone)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+impl Format {
+    pub fn parse(string: &str) -> Self {
+        match string {
+            "table" => Self::Table,
+            "json" => Self::Json,
+            _ => panic!("unknown report format: {string} (expected table or json)"),
+        }
+    }
+}
+
+/// The first pull from the bag that couldn't have happened
+#[derive(Debug, Copy, Clone)]
+struct Violation<'a> {
+    round: usize, // 1-based, like the puzzle text
+    color: &'a str,
+    count: u32,
+    limit: u32,
+}
+
+impl<'a> Violation<'a> {
+    fn find(game: &Game<'a>, bag: &Round) -> Option<Self> {
+        game.rounds.iter().enumerate().find_map(|(i, round)| {
+            let (color, count) = round.first_excess(bag)?;
+            let limit = bag.count(color);
+            Some(Self {
+                round: i + 1,
+                color,
+                count,
+                limit,
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+struct GameReport<'a> {
+    id: u32,
+    violation: Option<Violation<'a>>,
+    fewest: Round<'a>,
+    power: u32,
+}
+
+#[derive(Debug)]
+pub struct Report<'a> {
+    games: Vec<GameReport<'a>>,
+    /// The smallest bag that makes every game possible
+    covering_bag: Round<'a>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(games: &[Game<'a>], bag: &Round) -> Self {
+        let games: Vec<GameReport> = games
+            .iter()
+            .map(|game| {
+                let fewest = game.fewest_cubes_possible();
+                GameReport {
+                    id: game.id,
+                    violation: Violation::find(game, bag),
+                    power: fewest.power(bag),
+                    fewest,
+                }
+            })
+            .collect();
+
+        let mut covering_bag = Round::default();
+        for game in &games {
+            covering_bag.merge_max(&game.fewest);
+        }
+
+        Self {
+            games,
+            covering_bag,
+        }
+    }
+
+    fn possible(&self) -> impl Iterator<Item = &GameReport<'a>> {
+        self.games.iter().filter(|game| game.violation.is_none())
+    }
+
+    fn id_sum(&self) -> u32 {
+        self.possible().map(|game| game.id).sum()
+    }
+
+    fn power_sum(&self) -> u32 {
+        self.games.iter().map(|game| game.power).sum()
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Table => self.table(),
+            Format::Json => self.json(),
+        }
+    }
+
+    fn table(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(
+            out,
+            "{:>4}  {:<8}  {:<28}  {:>6}  minimal bag",
+            "game", "possible", "first violation", "power"
+        )
+        .unwrap();
+
+        for game in &self.games {
+            let violation = match game.violation {
+                Some(v) => format!("round {}: {} {} > {}", v.round, v.count, v.color, v.limit),
+                None => String::from("-"),
+            };
+            let possible = if game.violation.is_none() {
+                "yes"
+            } else {
+                "no"
+            };
+
+            writeln!(
+                out,
+                "{:>4}  {:<8}  {:<28}  {:>6}  {}",
+                game.id,
+                possible,
+                violation,
+                game.power,
+                describe(&game.fewest),
+            )
+            .unwrap();
+        }
+
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "possible games: {}/{}",
+            self.possible().count(),
+            self.games.len()
+        )
+        .unwrap();
+        writeln!(out, "sum of possible ids: {}", self.id_sum()).unwrap();
+        writeln!(out, "sum of powers: {}", self.power_sum()).unwrap();
+        writeln!(
+            out,
+            "bag that makes every game possible: {}",
+            describe(&self.covering_bag)
+        )
+        .unwrap();
+
+        out
+    }
+
+    fn json(&self) -> String {
+        let games: Vec<String> = self
+            .games
+            .iter()
+            .map(|game| {
+                let violation = match game.violation {
+                    Some(v) => format!(
+                        r#"{{"round":{},"color":{},"count":{},"limit":{}}}"#,
+                        v.round,
+                        json_string(v.color),
+                        v.count,
+                        v.limit
+                    ),
+                    None => String::from("null"),
+                };
+
+                format!(
+                    r#"{{"id":{},"possible":{},"violation":{},"minimal_bag":{},"power":{}}}"#,
+                    game.id,
+                    game.violation.is_none(),
+                    violation,
+                    json_object(&game.fewest),
+                    game.power,
+                )
+            })
+            .collect();
+
+        format!(
+            r#"{{"games":[{}],"possible_games":{},"id_sum":{},"power_sum":{},"covering_bag":{}}}"#,
+            games.join(","),
+            self.possible().count(),
+            self.id_sum(),
+            self.power_sum(),
+            json_object(&self.covering_bag),
+        ) + "\n"
+    }
+}
+
+/// Same format as the puzzle input, e.g. "4 red, 2 green, 6 blue"
+fn describe(round: &Round) -> String {
+    let pulls: Vec<String> = round
+        .cubes
+        .iter()
+        .map(|(color, count)| format!("{count} {color}"))
+        .collect();
+
+    pulls.join(", ")
+}
+
+fn json_object(round: &Round) -> String {
+    let fields: Vec<String> = round
+        .cubes
+        .iter()
+        .map(|(color, count)| format!("{}:{count}", json_string(color)))
+        .collect();
+
+    format!("{{{}}}", fields.join(","))
+}
+
+fn json_string(string: &str) -> String {
+    let mut out = String::from('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
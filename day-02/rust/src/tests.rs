@@ -0,0 +1,135 @@
+use super::report::{Format, Report};
+use super::{part1, part2, Game, Round, DEFAULT_BAG};
+
+fn example_games() -> Vec<Game<'static>> {
+    include_str!("../../test_input.txt")
+        .lines()
+        .map(Game::parse)
+        .collect()
+}
+
+const YELLOW: &str = "\
+Game 1: 1 red, 2 green, 2 yellow; 3 blue
+Game 2: 4 red, 1 green, 1 blue; 3 yellow
+Game 3: 5 red, 5 green, 5 blue";
+
+#[test]
+fn example() {
+    let input = include_str!("../../test_input.txt");
+    let bag = Round::parse(DEFAULT_BAG);
+
+    assert_eq!(part1(input, &bag), 8);
+    assert_eq!(part2(input, &bag), 2286);
+}
+
+#[test]
+fn power_with_a_missing_color() {
+    let bag = Round::parse(DEFAULT_BAG);
+    let game = Game::parse("Game 1: 3 red, 4 green; 1 red");
+
+    assert_eq!(game.fewest_cubes_possible().power(&bag), 0);
+}
+
+#[test]
+fn custom_bag() {
+    let bag = Round::parse("12 red, 13 green, 14 blue, 2 yellow");
+
+    // Game 2 pulls too many yellows and game 3 has none to multiply by
+    assert_eq!(part1(YELLOW, &bag), 1 + 3);
+    assert_eq!(part2(YELLOW, &bag), 12 + 12);
+}
+
+#[test]
+fn color_missing_from_the_bag() {
+    let bag = Round::parse(DEFAULT_BAG);
+
+    // Any yellow at all is too many but it doesn't count towards the power
+    assert_eq!(part1(YELLOW, &bag), 3);
+    assert_eq!(part2(YELLOW, &bag), 6 + 4 + 125);
+}
+
+#[test]
+fn first_excess_in_pull_order() {
+    let bag = Round::parse("1 red, 1 green, 1 blue");
+    let round = Round::parse("5 red, 1 blue, 9 green");
+
+    // Green comes first alphabetically but red was pulled first
+    assert_eq!(round.first_excess(&bag), Some(("red", 5)));
+    assert!(!round.possible(&bag));
+    assert_eq!(Round::parse("1 blue, 1 red").first_excess(&bag), None);
+}
+
+#[test]
+fn example_table() {
+    let bag = Round::parse("4 red, 3 green, 15 blue");
+    let report = Report::new(&example_games(), &bag);
+
+    assert_eq!(
+        report.render(Format::Table),
+        "\
+game  possible  first violation                power  minimal bag
+   1  yes       -                                 48  6 blue, 2 green, 4 red
+   2  yes       -                                 12  4 blue, 3 green, 1 red
+   3  no        round 1: 8 green > 3            1560  6 blue, 13 green, 20 red
+   4  no        round 2: 6 red > 4               630  15 blue, 3 green, 14 red
+   5  no        round 1: 6 red > 4                36  2 blue, 3 green, 6 red
+
+possible games: 2/5
+sum of possible ids: 3
+sum of powers: 2286
+bag that makes every game possible: 15 blue, 13 green, 20 red
+"
+    );
+}
+
+#[test]
+fn example_json() {
+    let bag = Round::parse(DEFAULT_BAG);
+    let games = example_games();
+    let report = Report::new(&games[2..4], &bag);
+
+    assert_eq!(
+        report.render(Format::Json),
+        concat!(
+            r#"{"games":["#,
+            r#"{"id":3,"possible":false,"#,
+            r#""violation":{"round":1,"color":"red","count":20,"limit":12},"#,
+            r#""minimal_bag":{"blue":6,"green":13,"red":20},"power":1560},"#,
+            r#"{"id":4,"possible":false,"#,
+            r#""violation":{"round":3,"color":"blue","count":15,"limit":14},"#,
+            r#""minimal_bag":{"blue":15,"green":3,"red":14},"power":630}],"#,
+            r#""possible_games":0,"id_sum":0,"power_sum":2190,"#,
+            r#""covering_bag":{"blue":15,"green":13,"red":20}}"#,
+            "\n",
+        )
+    );
+}
+
+#[test]
+fn covering_bag() {
+    let input = include_str!("../../test_input.txt");
+    let games = example_games();
+
+    // Every game is possible with the covering bag, and none of its colors
+    // could be any smaller
+    let json = Report::new(&games, &Round::parse(DEFAULT_BAG)).render(Format::Json);
+    let covering = r#""covering_bag":{"blue":15,"green":13,"red":20}"#;
+    assert!(json.ends_with(&format!("{covering}}}\n")));
+
+    let bag = Round::parse("20 red, 13 green, 15 blue");
+    assert_eq!(part1(input, &bag), 15);
+    for smaller in [
+        "19 red, 13 green, 15 blue",
+        "20 red, 12 green, 15 blue",
+        "20 red, 13 green, 14 blue",
+    ] {
+        assert!(part1(input, &Round::parse(smaller)) < 15);
+    }
+
+    // Colors that only some games pull still end up in the covering bag
+    let yellow: Vec<Game> = YELLOW.lines().map(Game::parse).collect();
+    let table = Report::new(&yellow, &bag).render(Format::Table);
+    assert!(
+        table.ends_with("bag that makes every game possible: 5 blue, 5 green, 5 red, 3 yellow\n")
+    );
+}
@@ -1,78 +1,90 @@
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+/// The size of the grid that a point lives in
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bounds {
+    pub height: usize,
+    pub width: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Point {
     pub y: usize,
     pub x: usize,
 }
 
 impl Point {
-    pub fn neighbors<const N: usize>(self) -> impl Iterator<Item = Self> {
+    pub fn neighbors(self, bounds: Bounds) -> impl Iterator<Item = Self> {
         [
-            self.up_left::<N>(),
-            self.up::<N>(),
-            self.up_right::<N>(),
-            self.left::<N>(),
-            self.right::<N>(),
-            self.down_left::<N>(),
-            self.down::<N>(),
-            self.down_right::<N>(),
+            self.up_left(),
+            self.up(),
+            self.up_right(bounds),
+            self.left(),
+            self.right(bounds),
+            self.down_left(bounds),
+            self.down(bounds),
+            self.down_right(bounds),
         ]
         .into_iter()
         .flatten()
     }
 
-    fn up_left<const N: usize>(self) -> Option<Self> {
+    fn up_left(self) -> Option<Self> {
         self.y
             .checked_sub(1)
             .zip(self.x.checked_sub(1))
             .map(|(y, x)| Self { y, x })
     }
 
-    fn up<const N: usize>(self) -> Option<Self> {
+    fn up(self) -> Option<Self> {
         self.y.checked_sub(1).map(|y| Self { y, ..self })
     }
 
-    fn up_right<const N: usize>(self) -> Option<Self> {
+    fn up_right(self, bounds: Bounds) -> Option<Self> {
         self.y
             .checked_sub(1)
-            .zip(self.x.bounded_add::<N>(1))
+            .zip(self.x.bounded_add(1, bounds.width))
             .map(|(y, x)| Self { y, x })
     }
 
-    pub fn left<const N: usize>(&self) -> Option<Self> {
+    pub fn left(&self) -> Option<Self> {
         self.x.checked_sub(1).map(|x| Self { x, ..*self })
     }
 
-    pub fn right<const N: usize>(&self) -> Option<Self> {
-        self.x.bounded_add::<N>(1).map(|x| Self { x, ..*self })
+    pub fn right(&self, bounds: Bounds) -> Option<Self> {
+        self.x
+            .bounded_add(1, bounds.width)
+            .map(|x| Self { x, ..*self })
     }
 
-    fn down_left<const N: usize>(self) -> Option<Self> {
+    fn down_left(self, bounds: Bounds) -> Option<Self> {
         self.y
-            .bounded_add::<N>(1)
+            .bounded_add(1, bounds.height)
             .zip(self.x.checked_sub(1))
             .map(|(y, x)| Self { y, x })
     }
 
-    fn down<const N: usize>(self) -> Option<Self> {
-        self.y.bounded_add::<N>(1).map(|y| Self { y, ..self })
+    fn down(self, bounds: Bounds) -> Option<Self> {
+        self.y
+            .bounded_add(1, bounds.height)
+            .map(|y| Self { y, ..self })
     }
 
-    fn down_right<const N: usize>(self) -> Option<Self> {
+    fn down_right(self, bounds: Bounds) -> Option<Self> {
         self.y
-            .bounded_add::<N>(1)
-            .zip(self.x.bounded_add::<N>(1))
+            .bounded_add(1, bounds.height)
+            .zip(self.x.bounded_add(1, bounds.width))
             .map(|(y, x)| Self { y, x })
     }
 }
 
 trait BoundedAdd: Copy {
-    fn bounded_add<const N: usize>(self, other: Self) -> Option<Self>;
+    fn bounded_add(self, other: Self, max: Self) -> Option<Self>;
 }
 
 impl BoundedAdd for usize {
-    fn bounded_add<const N: usize>(self, other: Self) -> Option<Self> {
+    /// Returns `None` if the sum isn't strictly less than `max`
+    fn bounded_add(self, other: Self, max: Self) -> Option<Self> {
         let sum = self + other;
-        match sum < N {
+        match sum < max {
             true => Some(sum),
             false => None,
         }
@@ -0,0 +1,165 @@
+use std::collections::{BTreeSet, HashMap};
+use std::iter::repeat;
+use std::ops::Range;
+
+use crate::point::{Bounds, Point};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    pub character: char,
+    pub point: Point,
+}
+
+impl Symbol {
+    fn new(character: char, point: Point) -> Option<Self> {
+        match character {
+            '.' | '0'..='9' => None,
+            _ => Some(Self { character, point }),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Number {
+    pub point: Point, // left-most point
+    pub length: usize,
+    pub value: u32,
+}
+
+impl Number {
+    /// The columns that this number's digits occupy
+    pub fn span(&self) -> Range<usize> {
+        self.point.x..(self.point.x + self.length)
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.span().map(|x| Point { x, ..self.point })
+    }
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    bounds: Bounds,
+    numbers: Vec<Number>,
+    symbols: HashMap<Point, Symbol>,
+    /// Which number (by index into `numbers`), if any, covers each point
+    number_at: Vec<Vec<Option<usize>>>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Self {
+        let grid: Vec<Vec<char>> = input
+            .lines()
+            .map(|line| line.chars().collect::<Vec<char>>())
+            .collect();
+
+        let bounds = Bounds {
+            height: grid.len(),
+            width: grid.first().map_or(0, Vec::len),
+        };
+        assert!(
+            grid.iter().all(|row| row.len() == bounds.width),
+            "schematic rows must all be the same width"
+        );
+
+        let symbols = grid
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| repeat(y).zip(row.iter().copied().enumerate()))
+            .filter_map(|(y, (x, c))| Symbol::new(c, Point { y, x }))
+            .map(|symbol| (symbol.point, symbol))
+            .collect();
+
+        let mut numbers = vec![];
+        let mut number_at = vec![vec![None; bounds.width]; bounds.height];
+
+        for (y, row) in grid.iter().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let length = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+                if length == 0 {
+                    x += 1;
+                    continue;
+                }
+
+                let digits = row[x..x + length].iter().filter_map(|c| c.to_digit(10));
+                numbers.push(Number {
+                    point: Point { y, x },
+                    length,
+                    value: digits_to_number(digits),
+                });
+                number_at[y][x..x + length].fill(Some(numbers.len() - 1));
+
+                x += length;
+            }
+        }
+
+        Self {
+            bounds,
+            numbers,
+            symbols,
+            number_at,
+        }
+    }
+
+    /// Every number in the schematic, in reading order
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    /// Every symbol touching `number`, in reading order
+    pub fn symbols_adjacent_to(&self, number: &Number) -> Vec<Symbol> {
+        let neighbors: BTreeSet<Point> = number
+            .points()
+            .flat_map(|point| point.neighbors(self.bounds))
+            .collect();
+
+        neighbors
+            .into_iter()
+            .filter_map(|point| self.symbols.get(&point).copied())
+            .collect()
+    }
+
+    /// Every distinct number touching `symbol`, in reading order
+    pub fn numbers_adjacent_to(&self, symbol: &Symbol) -> Vec<Number> {
+        let indices: BTreeSet<usize> = symbol
+            .point
+            .neighbors(self.bounds)
+            .filter_map(|Point { y, x }| self.number_at[y][x])
+            .collect();
+
+        indices.into_iter().map(|i| self.numbers[i]).collect()
+    }
+
+    /// Every symbol drawn as `character` along with the numbers touching it
+    pub fn numbers_adjacent_to_char(
+        &self,
+        character: char,
+    ) -> impl Iterator<Item = (Symbol, Vec<Number>)> + '_ {
+        let mut symbols: Vec<Symbol> = self
+            .symbols
+            .values()
+            .filter(|symbol| symbol.character == character)
+            .copied()
+            .collect();
+        symbols.sort_by_key(|symbol| symbol.point);
+
+        symbols
+            .into_iter()
+            .map(|symbol| (symbol, self.numbers_adjacent_to(&symbol)))
+    }
+
+    /// Symbols drawn as `character` that touch exactly `k` numbers
+    pub fn gears(
+        &self,
+        character: char,
+        k: usize,
+    ) -> impl Iterator<Item = (Symbol, Vec<Number>)> + '_ {
+        self.numbers_adjacent_to_char(character)
+            .filter(move |(_, numbers)| numbers.len() == k)
+    }
+}
+
+fn digits_to_number(digits: impl IntoIterator<Item = u32>) -> u32 {
+    digits.into_iter().fold(0, |acc, elm| acc * 10 + elm)
+}
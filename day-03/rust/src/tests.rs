@@ -0,0 +1,110 @@
+use super::point::Point;
+use super::schematic::{Number, Schematic};
+use super::{gear_ratios, part1};
+
+const EXAMPLE: &str = include_str!("../../test_input.txt");
+
+fn values(numbers: &[Number]) -> Vec<u32> {
+    numbers.iter().map(|number| number.value).collect()
+}
+
+#[test]
+fn example() {
+    let schematic = Schematic::parse(EXAMPLE);
+
+    assert_eq!(part1(&schematic), 4361);
+    assert_eq!(gear_ratios(&schematic, '*', 2), 467835);
+}
+
+#[test]
+fn example_queries() {
+    let schematic = Schematic::parse(EXAMPLE);
+    let numbers = schematic.numbers();
+
+    assert_eq!(numbers.len(), 10);
+    assert_eq!((numbers[0].value, numbers[0].span()), (467, 0..3));
+    assert_eq!((numbers[1].value, numbers[1].span()), (114, 5..8));
+
+    let symbols = schematic.symbols_adjacent_to(&numbers[0]);
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(
+        (symbols[0].character, symbols[0].point),
+        ('*', Point { y: 1, x: 3 })
+    );
+    assert!(schematic.symbols_adjacent_to(&numbers[1]).is_empty());
+
+    let hashes: Vec<_> = schematic.numbers_adjacent_to_char('#').collect();
+    assert_eq!(hashes.len(), 1);
+    assert_eq!(hashes[0].0.point, Point { y: 3, x: 6 });
+    assert_eq!(values(&hashes[0].1), [633]);
+}
+
+#[test]
+fn wide_schematic() {
+    let schematic = Schematic::parse(
+        "\
+..........12
+...........*
+7*.........3",
+    );
+
+    // 3 sits under the * in the last column so it counts too
+    assert_eq!(part1(&schematic), 12 + 7 + 3);
+    let stars: Vec<Vec<u32>> = schematic
+        .numbers_adjacent_to_char('*')
+        .map(|(_, numbers)| values(&numbers))
+        .collect();
+    assert_eq!(stars, [vec![12, 3], vec![7]]);
+}
+
+#[test]
+fn tall_schematic() {
+    let schematic = Schematic::parse(
+        "\
+1..
+*..
+...
+...
+...
+...
+..#
+.99",
+    );
+
+    assert_eq!(part1(&schematic), 1 + 99);
+    assert_eq!(gear_ratios(&schematic, '#', 1), 99);
+}
+
+#[test]
+#[should_panic(expected = "schematic rows must all be the same width")]
+fn ragged_schematic() {
+    Schematic::parse("1*\n2");
+}
+
+#[test]
+fn gears_with_other_counts() {
+    let schematic = Schematic::parse(EXAMPLE);
+
+    // The * next to 617 only touches that one number
+    let single: Vec<_> = schematic.gears('*', 1).collect();
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].0.point, Point { y: 4, x: 3 });
+    assert_eq!(gear_ratios(&schematic, '*', 1), 617);
+    assert_eq!(gear_ratios(&schematic, '*', 3), 0);
+
+    let schematic = Schematic::parse(
+        "\
+2.3..
+.*...
+4...*
+..10.",
+    );
+
+    assert_eq!(gear_ratios(&schematic, '*', 3), 2 * 3 * 4);
+    assert_eq!(gear_ratios(&schematic, '*', 1), 10);
+    assert_eq!(gear_ratios(&schematic, '*', 2), 0);
+
+    // The same number touching a gear twice only counts once
+    let schematic = Schematic::parse("123\n.*.\n...");
+    assert_eq!(values(&schematic.gears('*', 1).next().unwrap().1), [123]);
+}
@@ -0,0 +1,202 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write;
+
+use crate::Card;
+
+/// Which cards a card with `n` winners hands out copies of
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CopyRule {
+    /// The next `n` cards; running off the end of the table is an error
+    Next,
+    /// The next `n` cards, silently stopping at the end of the table
+    Capped,
+    /// The next `n` cards, wrapping back around to the first card
+    WrapAround,
+}
+
+impl CopyRule {
+    pub fn parse(string: &str) -> Self {
+        match string {
+            "next" => Self::Next,
+            "capped" => Self::Capped,
+            "wrap" => Self::WrapAround,
+            _ => panic!("unknown copy rule: {string} (expected next, capped, or wrap)"),
+        }
+    }
+
+    fn targets(self, source: usize, n: u32, table_size: usize) -> Result<Vec<usize>, Error> {
+        let next = (source + 1..).take(n as usize);
+
+        match self {
+            Self::Next => next
+                .map(|target| match target < table_size {
+                    true => Ok(target),
+                    false => Err(Error::RunsOffTable { source }),
+                })
+                .collect(),
+            Self::Capped => Ok(next.take_while(|&target| target < table_size).collect()),
+            Self::WrapAround => Ok(next.map(|target| target % table_size).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Card (by index) won copies of cards past the end of the table
+    RunsOffTable { source: usize },
+    /// Cards won copies of each other in a loop so there's no end to it
+    NeverEnds,
+    /// More card instances than fit in a `u64`
+    Overflow,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RunsOffTable { source } => {
+                write!(f, "card #{} wins copies past the end", source + 1)
+            }
+            Self::NeverEnds => write!(f, "cards win copies of each other forever"),
+            Self::Overflow => write!(f, "too many cards to count"),
+        }
+    }
+}
+
+/// A full run of part 2 that remembers where every copy came from
+#[derive(Debug)]
+pub struct Cascade {
+    ids: Vec<u32>,
+    /// How many instances of each card we end up with (including the original)
+    copies: Vec<u64>,
+    /// For each card, how many of its copies were won from each source card
+    sources: Vec<BTreeMap<usize, u64>>,
+    /// How many wins removed from an original each card is (0 for no copies)
+    depth: Vec<u32>,
+    /// How many copies of other cards each card hands out
+    handed_out: Vec<u64>,
+}
+
+impl Cascade {
+    pub fn simulate(cards: &[Card], rule: CopyRule) -> Result<Self, Error> {
+        let ids = cards.iter().map(|card| card.id).collect();
+        let targets: Vec<Vec<usize>> = cards
+            .iter()
+            .enumerate()
+            .map(|(source, card)| rule.targets(source, card.winning_count(), cards.len()))
+            .collect::<Result<_, _>>()?;
+
+        // A card's count is final once every card that wins copies of it has
+        // been counted, so visit them in topological order.
+        let mut pending = vec![0; cards.len()];
+        for &target in targets.iter().flatten() {
+            pending[target] += 1;
+        }
+        let mut ready: VecDeque<usize> = (0..cards.len()).filter(|&c| pending[c] == 0).collect();
+
+        let mut copies = vec![1u64; cards.len()];
+        let mut sources = vec![BTreeMap::new(); cards.len()];
+        let mut depth = vec![0; cards.len()];
+        let mut handed_out = vec![0u64; cards.len()];
+        let mut visited = 0;
+
+        while let Some(source) = ready.pop_front() {
+            visited += 1;
+            let count = copies[source];
+
+            for &target in &targets[source] {
+                copies[target] = copies[target].checked_add(count).ok_or(Error::Overflow)?;
+                let from_source = sources[target].entry(source).or_insert(0u64);
+                *from_source = from_source.checked_add(count).ok_or(Error::Overflow)?;
+                handed_out[source] = handed_out[source]
+                    .checked_add(count)
+                    .ok_or(Error::Overflow)?;
+                depth[target] = depth[target].max(depth[source] + 1);
+
+                pending[target] -= 1;
+                if pending[target] == 0 {
+                    ready.push_back(target);
+                }
+            }
+        }
+
+        if visited < cards.len() {
+            return Err(Error::NeverEnds);
+        }
+
+        Ok(Self {
+            ids,
+            copies,
+            sources,
+            depth,
+            handed_out,
+        })
+    }
+
+    pub fn total(&self) -> Result<u64, Error> {
+        self.copies
+            .iter()
+            .try_fold(0u64, |total, &copies| total.checked_add(copies))
+            .ok_or(Error::Overflow)
+    }
+
+    /// The longest chain of cards winning copies of cards winning copies...
+    pub fn depth(&self) -> u32 {
+        self.depth.iter().copied().max().unwrap_or(0)
+    }
+
+    /// How many copies of other cards each card handed out, biggest first
+    fn contributions(&self) -> Vec<(usize, u64)> {
+        let mut contributions: Vec<(usize, u64)> =
+            self.handed_out.iter().copied().enumerate().collect();
+
+        contributions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        contributions
+    }
+
+    /// Renders the `top` cards that contributed the most copies
+    pub fn summary(&self, top: usize) -> String {
+        let mut out = String::new();
+
+        writeln!(out, "cards: {}", self.copies.len()).unwrap();
+        match self.total() {
+            Ok(total) => writeln!(out, "total instances: {total}").unwrap(),
+            Err(error) => writeln!(out, "total instances: {error}").unwrap(),
+        }
+        writeln!(out, "cascade depth: {}", self.depth()).unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "{:>5}  {:>12}  {:>10}  {:>5}  biggest sources",
+            "card", "contributed", "instances", "depth"
+        )
+        .unwrap();
+
+        for (card, contributed) in self.contributions().into_iter().take(top) {
+            let mut sources: Vec<(usize, u64)> =
+                self.sources[card].iter().map(|(&s, &c)| (s, c)).collect();
+            sources.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+            let mut sources: Vec<String> = sources
+                .into_iter()
+                .take(3)
+                .map(|(source, count)| format!("#{} x{count}", self.ids[source]))
+                .collect();
+            if sources.is_empty() {
+                sources.push(String::from("-"));
+            }
+
+            writeln!(
+                out,
+                "{:>5}  {:>12}  {:>10}  {:>5}  {}",
+                self.ids[card],
+                contributed,
+                self.copies[card],
+                self.depth[card],
+                sources.join(", "),
+            )
+            .unwrap();
+        }
+
+        out
+    }
+}
@@ -1,7 +1,37 @@
+mod cascade;
+mod scoring;
+use cascade::{Cascade, CopyRule};
+use scoring::Scoring;
+use std::collections::HashSet;
+
+/// A bitmask when every winning number is small enough for one, which they
+/// are in the real input
+#[derive(Debug)]
+enum Winning {
+    Mask(u128),
+    Set(HashSet<u32>),
+}
+
+impl Winning {
+    fn new(numbers: &[u32]) -> Self {
+        match numbers.iter().all(|&num| num < u128::BITS) {
+            true => Self::Mask(numbers.iter().fold(0, |mask, num| mask | (1 << num))),
+            false => Self::Set(numbers.iter().copied().collect()),
+        }
+    }
+
+    fn contains(&self, num: u32) -> bool {
+        match self {
+            Self::Mask(mask) => num < u128::BITS && mask & (1 << num) != 0,
+            Self::Set(set) => set.contains(&num),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Card {
     id: u32,
-    winning: Vec<u32>,
+    winning: Winning,
     have: Vec<u32>,
 }
 
@@ -13,10 +43,11 @@ impl Card {
         let id = id.parse().unwrap();
         let (winning, have) = line.split_once(" | ").unwrap();
 
-        let winning = winning
+        let winning: Vec<u32> = winning
             .split_whitespace()
             .map(|num| num.parse().unwrap())
             .collect();
+        let winning = Winning::new(&winning);
 
         let have = have
             .split_whitespace()
@@ -26,17 +57,15 @@ impl Card {
         Self { id, winning, have }
     }
 
-    fn value(&self) -> u32 {
-        match self.winning_count() {
-            0 => 0,
-            winning_numbers => 2u32.pow(winning_numbers - 1),
-        }
+    /// Returns `None` if the value doesn't fit in a `u64`
+    fn value(&self, scoring: &Scoring) -> Option<u64> {
+        scoring.points(self.winning_count())
     }
 
     fn winning_count(&self) -> u32 {
         self.have
             .iter()
-            .filter(|have| self.winning.contains(have))
+            .filter(|&&have| self.winning.contains(have))
             .count() as u32
     }
 }
@@ -45,27 +74,49 @@ fn main() {
     // let input = include_str!("../../test_input.txt");
     let input = include_str!("../../input.txt");
 
+    let mut scoring = Scoring::Doubling;
+    let mut rule = CopyRule::Next;
+    let mut summary = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--scoring" => scoring = Scoring::parse(&args.next().expect("--scoring needs a rule")),
+            "--copies" => rule = CopyRule::parse(&args.next().expect("--copies needs a rule")),
+            "--summary" => summary = Some(10),
+            "--top" => summary = Some(args.next().expect("--top needs a count").parse().unwrap()),
+            _ => panic!("unknown argument: {arg}"),
+        }
+    }
+
     let cards: Vec<Card> = input.lines().map(Card::parse).collect();
-    let part1: u32 = cards.iter().map(Card::value).sum();
-    println!("part1 = {part1}");
-
-    let counts = vec![1; cards.len()];
-    let counts = cards.into_iter().fold(counts, |counts, card| {
-        let winning_count = card.winning_count();
-        let count = counts[card.id as usize - 1];
-
-        (1..=winning_count).fold(counts, |counts, id| {
-            let id = (id + card.id - 1) as usize;
-            let count = count + counts[id];
-            replace(counts, id, count)
-        })
+    let part1 = cards
+        .iter()
+        .try_fold(0u64, |sum, card| sum.checked_add(card.value(&scoring)?));
+    match part1 {
+        Some(part1) => println!("part1 = {part1}"),
+        None => println!("part1 = overflowed u64"),
+    }
+
+    let cascade = Cascade::simulate(&cards, rule).and_then(|cascade| {
+        let part2 = cascade.total()?;
+        Ok((cascade, part2))
     });
+    let cascade = match cascade {
+        Ok((cascade, part2)) => {
+            println!("part2 = {part2}");
+            cascade
+        }
+        Err(error) => {
+            println!("part2 = {error}");
+            return;
+        }
+    };
 
-    let part2: u32 = counts.into_iter().sum();
-    println!("part2 = {part2}");
+    if let Some(top) = summary {
+        print!("\n{}", cascade.summary(top));
+    }
 }
 
-fn replace<T>(mut v: Vec<T>, position: usize, item: T) -> Vec<T> {
-    v[position] = item;
-    v
-}
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,55 @@
+/// How many points a card with some number of winners is worth
+#[derive(Debug, Clone)]
+pub enum Scoring {
+    /// One point per winner
+    Linear,
+    /// One point for the first winner, doubled for each one after that
+    Doubling,
+    /// 1, 1, 2, 3, 5, ... points
+    Fibonacci,
+    /// Points for 1, 2, 3, ... winners; anything past the end scores the last entry
+    Custom(Vec<u64>),
+}
+
+impl Scoring {
+    /// Accepts `linear`, `doubling`, `fibonacci`, or `custom:1,2,4,...`
+    pub fn parse(string: &str) -> Self {
+        match string {
+            "linear" => Self::Linear,
+            "doubling" => Self::Doubling,
+            "fibonacci" => Self::Fibonacci,
+            _ => {
+                let table = string
+                    .strip_prefix("custom:")
+                    .unwrap_or_else(|| panic!("unknown scoring rule: {string}"));
+
+                Self::Custom(
+                    table
+                        .split(',')
+                        .map(|points| points.trim().parse().unwrap())
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    /// Returns `None` if the score doesn't fit in a `u64`
+    pub fn points(&self, winners: u32) -> Option<u64> {
+        if winners == 0 {
+            return Some(0);
+        }
+
+        match self {
+            Self::Linear => Some(winners.into()),
+            Self::Doubling => 1u64.checked_shl(winners - 1),
+            Self::Fibonacci => {
+                let (mut a, mut b) = (0u64, 1u64);
+                for _ in 1..winners {
+                    (a, b) = (b, a.checked_add(b)?);
+                }
+                Some(b)
+            }
+            Self::Custom(table) => table.get(winners as usize - 1).or(table.last()).copied(),
+        }
+    }
+}
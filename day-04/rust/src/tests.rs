@@ -0,0 +1,168 @@
+use super::cascade::{Cascade, CopyRule, Error};
+use super::scoring::Scoring;
+use super::Card;
+
+fn example() -> Vec<Card> {
+    include_str!("../../test_input.txt")
+        .lines()
+        .map(Card::parse)
+        .collect()
+}
+
+#[test]
+fn example_winners() {
+    let winners: Vec<u32> = example().iter().map(Card::winning_count).collect();
+    assert_eq!(winners, [4, 2, 2, 1, 0, 0]);
+}
+
+#[test]
+fn numbers_too_big_for_the_bitmask() {
+    let card = Card::parse("Card 1: 127 128 1000 4 | 1000 4 5 128 128 127");
+    assert_eq!(card.winning_count(), 5);
+
+    let card = Card::parse("Card 2:   3 127 | 127 128 3");
+    assert_eq!(card.winning_count(), 2);
+}
+
+#[test]
+fn example_cascade() {
+    let cascade = Cascade::simulate(&example(), CopyRule::Next).unwrap();
+
+    assert_eq!(cascade.total(), Ok(30));
+    assert_eq!(cascade.depth(), 4);
+}
+
+#[test]
+fn example_summary() {
+    let cascade = Cascade::simulate(&example(), CopyRule::Next).unwrap();
+
+    assert_eq!(
+        cascade.summary(10),
+        "\
+cards: 6
+total instances: 30
+cascade depth: 4
+
+ card   contributed   instances  depth  biggest sources
+    3             8           4      2  #2 x2, #1 x1
+    4             8           8      3  #3 x4, #2 x2, #1 x1
+    1             4           1      0  -
+    2             4           2      1  #1 x1
+    5             0          14      4  #4 x8, #3 x4, #1 x1
+    6             0           1      0  -
+"
+    );
+}
+
+fn cards(lines: &[&str]) -> Vec<Card> {
+    lines.iter().map(|line| Card::parse(line)).collect()
+}
+
+#[test]
+fn example_scores() {
+    let cards = example();
+    let score = |scoring: Scoring| -> Vec<Option<u64>> {
+        cards.iter().map(|card| card.value(&scoring)).collect()
+    };
+
+    let doubling = [8, 2, 2, 1, 0, 0].map(Some);
+    assert_eq!(score(Scoring::parse("doubling")), doubling);
+    let linear = [4, 2, 2, 1, 0, 0].map(Some);
+    assert_eq!(score(Scoring::parse("linear")), linear);
+    let fibonacci = [3, 1, 1, 1, 0, 0].map(Some);
+    assert_eq!(score(Scoring::parse("fibonacci")), fibonacci);
+    let custom = [5, 3, 3, 1, 0, 0].map(Some);
+    assert_eq!(score(Scoring::parse("custom:1, 3,5")), custom);
+}
+
+#[test]
+fn doubling_overflow() {
+    assert_eq!(Scoring::Doubling.points(63), Some(1 << 62));
+    assert_eq!(Scoring::Doubling.points(64), Some(1 << 63));
+    assert_eq!(Scoring::Doubling.points(65), None);
+    assert_eq!(Scoring::Doubling.points(u32::MAX), None);
+}
+
+#[test]
+fn fibonacci_overflow() {
+    // F(93) is the biggest Fibonacci number that fits in a u64
+    assert_eq!(
+        Scoring::Fibonacci.points(93),
+        Some(12_200_160_415_121_876_738)
+    );
+    assert_eq!(Scoring::Fibonacci.points(94), None);
+}
+
+#[test]
+fn custom_past_the_end() {
+    let scoring = Scoring::Custom(vec![1, 3, 7]);
+
+    assert_eq!(scoring.points(0), Some(0));
+    assert_eq!(scoring.points(3), Some(7));
+    assert_eq!(scoring.points(4), Some(7));
+    assert_eq!(scoring.points(u32::MAX), Some(7));
+}
+
+#[test]
+#[should_panic(expected = "unknown scoring rule")]
+fn unknown_scoring() {
+    Scoring::parse("quadratic");
+}
+
+#[test]
+fn runs_off_table() {
+    let cards = cards(&["Card 1: 1 | 1", "Card 2: 2 | 2"]);
+
+    let error = Cascade::simulate(&cards, CopyRule::Next).unwrap_err();
+    assert_eq!(error, Error::RunsOffTable { source: 1 });
+    assert_eq!(error.to_string(), "card #2 wins copies past the end");
+
+    // Capped just drops the copy of the card that isn't there
+    let cascade = Cascade::simulate(&cards, CopyRule::Capped).unwrap();
+    assert_eq!(cascade.total(), Ok(3));
+}
+
+#[test]
+fn never_ends() {
+    // Wrapping around, each card wins a copy of the other
+    let cards = cards(&["Card 1: 1 | 1", "Card 2: 2 | 2"]);
+
+    let error = Cascade::simulate(&cards, CopyRule::WrapAround).unwrap_err();
+    assert_eq!(error, Error::NeverEnds);
+    assert_eq!(error.to_string(), "cards win copies of each other forever");
+}
+
+#[test]
+fn capped_and_wrap_around() {
+    let cards = cards(&["Card 1: 1 | 2", "Card 2: 1 | 2", "Card 3: 1 2 | 1 2"]);
+
+    // Card 3 is last so it has nothing to hand out unless it wraps around
+    let capped = Cascade::simulate(&cards, CopyRule::Capped).unwrap();
+    assert_eq!((capped.total(), capped.depth()), (Ok(3), 0));
+
+    let wrapped = Cascade::simulate(&cards, CopyRule::WrapAround).unwrap();
+    assert_eq!((wrapped.total(), wrapped.depth()), (Ok(5), 1));
+
+    // The example never runs off the end so every rule agrees on it
+    for rule in [CopyRule::Next, CopyRule::Capped, CopyRule::WrapAround] {
+        let cascade = Cascade::simulate(&example(), rule).unwrap();
+        assert_eq!(cascade.total(), Ok(30));
+    }
+}
+
+#[test]
+fn overflow() {
+    // Each card doubles the copies of every card after it
+    let lines: Vec<String> = (1..=70)
+        .map(|id| {
+            let wins = 70 - id;
+            let numbers: Vec<String> = (1..=wins).map(|n| n.to_string()).collect();
+            let numbers = numbers.join(" ");
+            format!("Card {id}: {numbers} | {numbers}")
+        })
+        .collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+    let error = Cascade::simulate(&cards(&lines), CopyRule::Next).unwrap_err();
+    assert_eq!(error, Error::Overflow);
+}
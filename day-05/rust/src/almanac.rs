@@ -0,0 +1,115 @@
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+
+use crate::range_map::merge;
+use crate::{parse_map, RangeMap};
+
+/// One `X-to-Y map:` section
+#[derive(Debug)]
+pub struct CategoryMap<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub mappings: RangeMap,
+}
+
+#[derive(Debug)]
+pub struct Almanac<'a> {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<CategoryMap<'a>>,
+}
+
+impl<'a> Almanac<'a> {
+    pub fn parse(input: &'a str) -> Self {
+        let mut sections = input.split("\n\n");
+
+        let seeds = sections.next().unwrap();
+        let seeds = seeds
+            .strip_prefix("seeds: ")
+            .expect("almanac should start with seeds")
+            .split_whitespace()
+            .map(|num| num.parse().unwrap())
+            .collect();
+
+        let maps = sections
+            .filter(|section| !section.trim().is_empty())
+            .map(|section| {
+                let header = section.lines().next().unwrap();
+                let (from, to) = header
+                    .strip_suffix(" map:")
+                    .and_then(|header| header.split_once("-to-"))
+                    .unwrap_or_else(|| panic!("bad map header: {header}"));
+
+                CategoryMap {
+                    from,
+                    to,
+                    mappings: parse_map(section),
+                }
+            })
+            .collect();
+
+        Self { seeds, maps }
+    }
+
+    /// Seeds are listed in (start, length) pairs for part 2
+    pub fn seed_ranges(&self) -> Vec<Range<u64>> {
+        self.seeds
+            .chunks(2)
+            .map(|window| window[0]..(window[0] + window[1]))
+            .collect()
+    }
+
+    /// The shortest chain of maps that leads from one category to another
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<&CategoryMap<'a>>> {
+        let mut came_by: HashMap<&str, usize> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut path = vec![];
+                let mut category = to;
+                while category != from {
+                    let map = &self.maps[came_by[category]];
+                    path.push(map);
+                    category = map.from;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for (i, map) in self.maps.iter().enumerate() {
+                if map.from == category && map.to != from && !came_by.contains_key(map.to) {
+                    came_by.insert(map.to, i);
+                    queue.push_back(map.to);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// One map that goes all the way from one category to another, or `None`
+    /// if there's no way to get there
+    pub fn range_map(&self, from: &str, to: &str) -> Option<RangeMap> {
+        let path = self.path(from, to)?;
+
+        Some(
+            path.into_iter()
+                .fold(RangeMap::default(), |map, next| map.compose(&next.mappings)),
+        )
+    }
+
+    /// Every `from` number that ends up somewhere in `range` once it's been
+    /// mapped to `to`, found by walking the maps backwards
+    pub fn preimage(&self, from: &str, to: &str, range: Range<u64>) -> Option<Vec<Range<u64>>> {
+        let path = self.path(from, to)?;
+
+        Some(path.into_iter().rev().fold(vec![range], |ranges, map| {
+            merge(
+                ranges
+                    .into_iter()
+                    .flat_map(|range| map.mappings.preimage(range))
+                    .collect(),
+            )
+        }))
+    }
+}
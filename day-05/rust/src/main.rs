@@ -1,7 +1,11 @@
-use std::collections::VecDeque;
+mod almanac;
+mod range_map;
+use almanac::Almanac;
+use range_map::RangeMap;
+
 use std::ops::Range;
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 struct Mapping {
     source_start: u64,
     dest_start: u64,
@@ -21,15 +25,15 @@ impl Mapping {
         (range.start + self.dest_start - self.source_start)
             ..(range.end + self.dest_start - self.source_start)
     }
-}
-
-type Map = Vec<Mapping>;
 
-fn lookup(number: u64, ranges: &[Mapping]) -> Option<u64> {
-    ranges
-        .iter()
-        .find(|mapping| mapping.source().contains(&number))
-        .map(|mapping| mapping.lookup(number))
+    /// The same mapping pointed the other way
+    fn inverse(&self) -> Self {
+        Self {
+            source_start: self.dest_start,
+            dest_start: self.source_start,
+            length: self.length,
+        }
+    }
 }
 
 fn parse_ranges(line: &str) -> Option<Mapping> {
@@ -47,97 +51,92 @@ fn parse_ranges(line: &str) -> Option<Mapping> {
     })
 }
 
-fn parse_map(string: &str) -> Map {
-    string.lines().skip(1).map_while(parse_ranges).collect()
+fn parse_map(string: &str) -> RangeMap {
+    let mappings = string.lines().skip(1).map_while(parse_ranges).collect();
+    RangeMap::new(mappings).unwrap_or_else(|error| panic!("bad map: {error}"))
 }
 
 fn main() {
     // let input = include_str!("../../test_input.txt");
     let input = include_str!("../../input.txt");
 
-    let mut sections = input.split("\n\n");
-
-    let seeds = sections.next().unwrap();
-    let seeds: Vec<u64> = seeds[7..]
-        .split(' ')
-        .map(|num| num.parse().unwrap())
-        .collect();
-
-    let seed_ranges: Vec<Range<u64>> = seeds
-        .chunks(2)
-        .map(|window| window[0]..(window[0] + window[1]))
-        .collect();
+    let mut target = String::from("location");
+    let mut reverse = None;
+    let mut preimage = None;
+    let mut verify = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--to" => target = args.next().expect("--to needs a category"),
+            "--reverse" => {
+                let number = args.next().expect("--reverse needs a number");
+                reverse = Some(number.parse::<u64>().unwrap());
+            }
+            "--preimage" => {
+                let range = args.next().expect("--preimage needs a range like 10..20");
+                let (start, end) = range.split_once("..").expect("range should be start..end");
+                preimage = Some(start.parse::<u64>().unwrap()..end.parse::<u64>().unwrap());
+            }
+            "--verify" => verify = true,
+            _ => panic!("unknown argument: {arg}"),
+        }
+    }
 
-    let seed_to_soil = parse_map(sections.next().unwrap());
-    let soil_to_fertilizer = parse_map(sections.next().unwrap());
-    let fertilizer_to_water = parse_map(sections.next().unwrap());
-    let water_to_light = parse_map(sections.next().unwrap());
-    let light_to_temperature = parse_map(sections.next().unwrap());
-    let temperature_to_humidity = parse_map(sections.next().unwrap());
-    let humidity_to_location = parse_map(sections.next().unwrap());
+    let almanac = Almanac::parse(input);
+    let seed_to_target = almanac
+        .range_map("seed", &target)
+        .unwrap_or_else(|| panic!("can't get from seed to {target}"));
 
-    let part1 = seeds
-        .into_iter()
-        .map(|seed| {
-            let soil = lookup(seed, &seed_to_soil).unwrap_or(seed);
-            let fertilizer = lookup(soil, &soil_to_fertilizer).unwrap_or(soil);
-            let water = lookup(fertilizer, &fertilizer_to_water).unwrap_or(fertilizer);
-            let light = lookup(water, &water_to_light).unwrap_or(water);
-            let temperature = lookup(light, &light_to_temperature).unwrap_or(light);
-            let humidity = lookup(temperature, &temperature_to_humidity).unwrap_or(temperature);
-            lookup(humidity, &humidity_to_location).unwrap_or(humidity)
-        })
+    let part1 = almanac
+        .seeds
+        .iter()
+        .map(|&seed| seed_to_target.get(seed))
         .min()
         .unwrap();
 
     println!("part1 = {part1}");
 
-    let soil = round(seed_ranges, &seed_to_soil);
-    let fertilizer = round(soil, &soil_to_fertilizer);
-    let water = round(fertilizer, &fertilizer_to_water);
-    let light = round(water, &water_to_light);
-    let temperature = round(light, &light_to_temperature);
-    let humidity = round(temperature, &temperature_to_humidity);
-    let location = round(humidity, &humidity_to_location);
+    let part2 = seed_to_target
+        .map_ranges(&almanac.seed_ranges())
+        .into_iter()
+        .map(|range| range.start)
+        .min()
+        .unwrap();
 
-    let part2 = location.into_iter().map(|range| range.start).min().unwrap();
     println!("part2 = {part2}");
-}
 
-fn round(coming_from: Vec<Range<u64>>, mappings: &[Mapping]) -> Vec<Range<u64>> {
-    let mut done = vec![];
-    let mut queue = VecDeque::from_iter(coming_from);
-
-    'queue: while let Some(seed_range) = queue.pop_front() {
-        for mapping in mappings {
-            match smart_overlap(&seed_range, &mapping.source()) {
-                OverlapResult::None => (),
-                OverlapResult::Full(range) => {
-                    done.push(mapping.translate(range));
-                    continue 'queue;
-                }
-                OverlapResult::Partial { overlap, leftovers } => {
-                    done.push(mapping.translate(overlap));
-                    queue.push_back(leftovers);
-                    continue 'queue;
-                }
-                OverlapResult::Split {
-                    overlap,
-                    left,
-                    right,
-                } => {
-                    done.push(mapping.translate(overlap));
-                    queue.extend([left, right]);
-                    continue 'queue;
-                }
+    if let Some(number) = reverse {
+        match seed_to_target.invert() {
+            Ok(target_to_seed) => {
+                println!("{target} {number} = seed {}", target_to_seed.get(number))
             }
+            Err(error) => println!("can't reverse: {error}"),
         }
+    }
 
-        // None of the mappings overlapped so pass through unaltered.
-        done.push(seed_range);
+    if let Some(range) = preimage {
+        let seeds = almanac.preimage("seed", &target, range.clone()).unwrap();
+        println!("{target} {range:?} comes from seeds {seeds:?}");
     }
 
-    done
+    if verify {
+        // Nothing below the answer should lead back to a seed but the answer should
+        let from_seed = |range: Range<u64>| {
+            let seeds = almanac.preimage("seed", &target, range).unwrap();
+            intersects(&seeds, &almanac.seed_ranges())
+        };
+        assert!(!from_seed(0..part2), "a lower {target} comes from a seed");
+        assert!(
+            from_seed(part2..part2 + 1),
+            "{target} {part2} doesn't come from a seed"
+        );
+        println!("part2 verified in reverse");
+    }
+}
+
+fn intersects(a: &[Range<u64>], b: &[Range<u64>]) -> bool {
+    a.iter()
+        .any(|a| b.iter().any(|b| a.start < b.end && b.start < a.end))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -0,0 +1,203 @@
+use std::ops::Range;
+
+use crate::{smart_overlap, Mapping, OverlapResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Two mappings claim some of the same source numbers
+    Overlap(Range<u64>, Range<u64>),
+    /// Some numbers are reached from more than one source
+    NotInvertible,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overlap(a, b) => write!(f, "source ranges {a:?} and {b:?} overlap"),
+            Self::NotInvertible => write!(f, "map sends more than one number to the same place"),
+        }
+    }
+}
+
+/// A piecewise-linear map: numbers inside a mapping's source range are
+/// shifted by it and everything else maps to itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    mappings: Vec<Mapping>, // sorted by source and never overlapping
+}
+
+impl RangeMap {
+    pub fn new(mut mappings: Vec<Mapping>) -> Result<Self, Error> {
+        mappings.retain(|mapping| mapping.length > 0);
+        mappings.sort_by_key(|mapping| mapping.source_start);
+
+        match mappings
+            .windows(2)
+            .find(|pair| pair[0].source().end > pair[1].source_start)
+        {
+            Some(pair) => Err(Error::Overlap(pair[0].source(), pair[1].source())),
+            None => Ok(Self { mappings }),
+        }
+    }
+
+    /// Index of the first mapping that doesn't end before `number`
+    fn position(&self, number: u64) -> usize {
+        self.mappings
+            .partition_point(|mapping| mapping.source().end <= number)
+    }
+
+    pub fn get(&self, number: u64) -> u64 {
+        match self.mappings.get(self.position(number)) {
+            Some(mapping) if mapping.source().contains(&number) => mapping.lookup(number),
+            _ => number,
+        }
+    }
+
+    pub fn map_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        ranges
+            .iter()
+            .flat_map(|range| self.pieces(range.clone()))
+            .map(|(_, dest)| dest)
+            .collect()
+    }
+
+    /// Cuts `range` up wherever the mapping changes and pairs each piece with
+    /// where it ends up.
+    fn pieces(&self, range: Range<u64>) -> Vec<(Range<u64>, Range<u64>)> {
+        let mut pieces = vec![];
+        let mut rest = range;
+
+        for mapping in &self.mappings[self.position(rest.start)..] {
+            if rest.is_empty() {
+                break;
+            }
+
+            match smart_overlap(&rest, &mapping.source()) {
+                // Every mapping from here on starts after the range ends
+                OverlapResult::None => break,
+                OverlapResult::Full(overlap) => {
+                    rest = overlap.end..overlap.end;
+                    pieces.push((overlap.clone(), mapping.translate(overlap)));
+                }
+                OverlapResult::Partial { overlap, leftovers }
+                    if leftovers.start < overlap.start =>
+                {
+                    rest = overlap.end..overlap.end;
+                    pieces.push((leftovers.clone(), leftovers));
+                    pieces.push((overlap.clone(), mapping.translate(overlap)));
+                }
+                OverlapResult::Partial { overlap, leftovers } => {
+                    rest = leftovers;
+                    pieces.push((overlap.clone(), mapping.translate(overlap)));
+                }
+                OverlapResult::Split {
+                    overlap,
+                    left,
+                    right,
+                } => {
+                    rest = right;
+                    pieces.push((left.clone(), left));
+                    pieces.push((overlap.clone(), mapping.translate(overlap)));
+                }
+            }
+        }
+
+        if !rest.is_empty() {
+            pieces.push((rest.clone(), rest));
+        }
+
+        pieces
+    }
+
+    /// A single map that does the same thing as applying `self` then `next`
+    pub fn compose(&self, next: &RangeMap) -> RangeMap {
+        let mut mappings = vec![];
+
+        for (source, middle) in self.pieces(0..u64::MAX) {
+            for (part, dest) in next.pieces(middle.clone()) {
+                let source_start = source.start + (part.start - middle.start);
+
+                // Leave out anything that ends up where it started
+                if source_start != dest.start {
+                    mappings.push(Mapping {
+                        source_start,
+                        dest_start: dest.start,
+                        length: dest.end - dest.start,
+                    });
+                }
+            }
+        }
+
+        RangeMap::new(mappings).expect("pieces of a map never overlap")
+    }
+
+    /// Only works if the mappings shuffle numbers around within the same set
+    /// since everything else maps to itself.
+    pub fn invert(&self) -> Result<RangeMap, Error> {
+        let inverse = RangeMap::new(self.mappings.iter().map(Mapping::inverse).collect())
+            .map_err(|_| Error::NotInvertible)?;
+
+        match self.covered() == inverse.covered() {
+            true => Ok(inverse),
+            false => Err(Error::NotInvertible),
+        }
+    }
+
+    /// Every number that ends up somewhere in `range`, as sorted ranges. Unlike
+    /// `invert` this works even if several numbers end up in the same place.
+    pub fn preimage(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut preimage = self.gaps(range.clone());
+
+        for inverse in self.mappings.iter().map(Mapping::inverse) {
+            let source = inverse.source();
+            let overlap = range.start.max(source.start)..range.end.min(source.end);
+            if !overlap.is_empty() {
+                preimage.push(inverse.translate(overlap));
+            }
+        }
+
+        merge(preimage)
+    }
+
+    /// The parts of `range` that no mapping touches
+    fn gaps(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut gaps = vec![];
+        let mut start = range.start;
+
+        for mapping in &self.mappings[self.position(range.start)..] {
+            if mapping.source_start >= range.end {
+                break;
+            }
+            if mapping.source_start > start {
+                gaps.push(start..mapping.source_start);
+            }
+            start = start.max(mapping.source().end);
+        }
+
+        if start < range.end {
+            gaps.push(start..range.end);
+        }
+
+        gaps
+    }
+
+    /// The source ranges of every mapping, with touching ranges merged
+    fn covered(&self) -> Vec<Range<u64>> {
+        merge(self.mappings.iter().map(Mapping::source).collect())
+    }
+}
+
+/// Sorts ranges and joins together any that overlap or touch
+pub fn merge(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<u64>> = vec![];
+
+    for range in ranges.into_iter().filter(|range| !range.is_empty()) {
+        match merged.last_mut() {
+            Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
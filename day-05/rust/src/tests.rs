@@ -1,5 +1,9 @@
+use super::almanac::Almanac;
+use super::range_map::{Error as RangeError, RangeMap};
 use super::smart_overlap;
+use super::Mapping;
 use super::OverlapResult;
+use std::ops::Range;
 
 #[test]
 fn smart_overlap_full() {
@@ -47,3 +51,136 @@ fn smart_overlap_partial() {
         smart_overlap(&(0..5), &(3..10))
     );
 }
+
+const TEST_INPUT: &str = include_str!("../../test_input.txt");
+
+fn lookup(almanac: &Almanac, from: &str, to: &str, number: u64) -> Option<u64> {
+    almanac.range_map(from, to).map(|map| map.get(number))
+}
+
+fn mapping(dest_start: u64, source_start: u64, length: u64) -> Mapping {
+    Mapping {
+        source_start,
+        dest_start,
+        length,
+    }
+}
+
+#[test]
+fn almanac_seed_to_location() {
+    let almanac = Almanac::parse(TEST_INPUT);
+    let seed_to_location = almanac.range_map("seed", "location").unwrap();
+
+    let locations: Vec<u64> = almanac
+        .seeds
+        .iter()
+        .map(|&seed| seed_to_location.get(seed))
+        .collect();
+    assert_eq!(vec![82, 43, 86, 35], locations);
+
+    let ranges = seed_to_location.map_ranges(&almanac.seed_ranges());
+    assert_eq!(Some(46), ranges.into_iter().map(|r| r.start).min());
+}
+
+#[test]
+fn almanac_intermediate_categories() {
+    let almanac = Almanac::parse(TEST_INPUT);
+
+    assert_eq!(Some(81), lookup(&almanac, "seed", "soil", 79));
+    assert_eq!(Some(74), lookup(&almanac, "seed", "light", 79));
+    assert_eq!(Some(78), lookup(&almanac, "temperature", "humidity", 78));
+    assert_eq!(Some(79), lookup(&almanac, "seed", "seed", 79));
+    assert_eq!(None, lookup(&almanac, "location", "seed", 79));
+    assert_eq!(None, lookup(&almanac, "seed", "fertiliser", 79));
+}
+
+#[test]
+fn almanac_reordered_sections() {
+    let (seeds, maps) = TEST_INPUT.split_once("\n\n").unwrap();
+    let mut maps: Vec<&str> = maps.trim_end().split("\n\n").collect();
+    maps.reverse();
+    let input = format!("{seeds}\n\n{}\n", maps.join("\n\n"));
+
+    let almanac = Almanac::parse(&input);
+    assert_eq!(Some(35), lookup(&almanac, "seed", "location", 13));
+}
+
+#[test]
+fn range_map_overlap() {
+    assert_eq!(
+        Err(RangeError::Overlap(0..10, 5..7)),
+        RangeMap::new(vec![mapping(100, 5, 2), mapping(50, 0, 10)])
+    );
+    assert!(RangeMap::new(vec![mapping(100, 5, 2), mapping(50, 0, 5)]).is_ok());
+}
+
+#[test]
+fn range_map_ranges() {
+    let map = RangeMap::new(vec![mapping(50, 98, 2), mapping(52, 50, 48)]).unwrap();
+
+    assert_eq!(
+        vec![0..10, 40..50, 52..62, 97..100, 50..52, 100..105],
+        map.map_ranges(&[0..10, 40..60, 95..105])
+    );
+}
+
+#[test]
+fn range_map_compose() {
+    let almanac = Almanac::parse(TEST_INPUT);
+    let seed_to_location = almanac.range_map("seed", "location").unwrap();
+    let steps: Vec<&RangeMap> = almanac.maps.iter().map(|map| &map.mappings).collect();
+
+    for seed in 0..120 {
+        let location = steps.iter().fold(seed, |number, map| map.get(number));
+        assert_eq!(location, seed_to_location.get(seed), "seed {seed}");
+    }
+}
+
+#[test]
+fn range_map_invert() {
+    let map = RangeMap::new(vec![mapping(50, 98, 2), mapping(52, 50, 48)]).unwrap();
+    let inverse = map.invert().unwrap();
+    for number in 0..120 {
+        assert_eq!(number, inverse.get(map.get(number)));
+    }
+
+    // 5 and 10 would both end up at 10
+    let map = RangeMap::new(vec![mapping(10, 5, 1)]).unwrap();
+    assert_eq!(Err(RangeError::NotInvertible), map.invert());
+}
+
+#[test]
+fn range_map_preimage() {
+    // Both 5 and 10 end up at 10 and nothing ends up at 5
+    let map = RangeMap::new(vec![mapping(10, 5, 1), mapping(100, 20, 10)]).unwrap();
+
+    assert_eq!(vec![5..6, 10..11], map.preimage(10..11));
+    assert_eq!(Vec::<Range<u64>>::new(), map.preimage(5..6));
+    assert_eq!(vec![0..5, 6..10], map.preimage(0..10));
+    assert_eq!(vec![5..26, 30..106], map.preimage(5..106));
+
+    for number in 0..200 {
+        let target = map.get(number);
+        assert!(map
+            .preimage(target..target + 1)
+            .iter()
+            .any(|r| r.contains(&number)));
+    }
+}
+
+#[test]
+fn almanac_preimage() {
+    let almanac = Almanac::parse(TEST_INPUT);
+    let seed_to_location = almanac.range_map("seed", "location").unwrap();
+
+    let seeds = almanac.preimage("seed", "location", 35..36).unwrap();
+    assert!(seeds.iter().any(|range| range.contains(&13)));
+    assert_eq!(
+        Some(seed_to_location.preimage(40..60)),
+        almanac.preimage("seed", "location", 40..60)
+    );
+
+    // Part 2's answer is the lowest location that leads back to a seed range
+    let seeds = almanac.preimage("seed", "location", 46..47).unwrap();
+    assert!(seeds.iter().any(|range| range.contains(&82)));
+}
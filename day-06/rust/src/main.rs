@@ -1,26 +1,73 @@
-fn main() {
-    // let input = include_str!("../../input.txt");
-    // let times = [41, 66, 72, 66];
-    // let distances = [244, 1047, 1228, 1040];
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Race {
+    time: u128,
+    record: u128,
+}
 
-    let times = [7, 15, 30];
-    let distances = [9, 40, 200];
+impl Race {
+    /// Part 1: each column is its own race
+    fn parse_all(input: &str) -> Vec<Self> {
+        let (times, records) = parse_lines(input);
+        let times = times.split_whitespace().map(|num| num.parse().unwrap());
+        let records = records.split_whitespace().map(|num| num.parse().unwrap());
 
-    let part1: usize = times
-        .into_iter()
-        .zip(distances)
-        .map(|(round_length, record)| attempt(round_length, record))
-        .product();
-    println!("part1 = {part1}");
+        times
+            .zip(records)
+            .map(|(time, record)| Self { time, record })
+            .collect()
+    }
+
+    /// Part 2: the spaces are just bad kerning so there's only one race
+    fn parse_kerned(input: &str) -> Self {
+        let (time, record) = parse_lines(input);
+        let unkern = |line: &str| line.replace(' ', "").parse().unwrap();
+
+        Self {
+            time: unkern(time),
+            record: unkern(record),
+        }
+    }
 
-    println!("part2 = {}", quadratic_formula(41667266.0, 244104712281040.0));
+    /// How many ways there are to hold the button and beat the record
+    fn ways_to_win(self) -> u128 {
+        let Self { time, record } = self;
+
+        // Holding for `held` goes `held * (time - held)` which is symmetric
+        // around (and biggest at) `time / 2`.
+        let beats = |held: u128| {
+            held.checked_mul(time - held)
+                .is_none_or(|distance| distance > record)
+        };
+
+        let half = time / 2;
+        if !beats(half) {
+            return 0;
+        }
+
+        let mut lower = match shortest_hold_estimate(time, record) {
+            Some(estimate) => estimate.min(half),
+            None => first_win_by_search(0, half, beats),
+        };
+
+        // The estimate can be off by one thanks to the rounding in isqrt
+        while lower > 0 && beats(lower - 1) {
+            lower -= 1;
+        }
+        while !beats(lower) {
+            lower += 1;
+        }
+
+        time - 2 * lower + 1
+    }
 }
 
-fn attempt(round_length: u64, record: u64) -> usize {
-    (1..round_length)
-        .map(|speed| speed * (round_length - speed))
-        .filter(|distance| *distance > record)
-        .count()
+fn parse_lines(input: &str) -> (&str, &str) {
+    let mut lines = input.lines();
+    let time = lines.next().and_then(|line| line.strip_prefix("Time:"));
+    let record = lines.next().and_then(|line| line.strip_prefix("Distance:"));
+
+    time.zip(record)
+        .expect("input should be Time: and Distance: lines")
 }
 
 // record = time_held * (round_length - time_held)
@@ -30,10 +77,42 @@ fn attempt(round_length: u64, record: u64) -> usize {
 //
 // a = 1, b = -round_length, c = record
 // x = (round_length ± sqrt(round_length^2 - 4 * record)) / 2
-fn quadratic_formula(round_length: f64, record: f64) -> f64 {
-    let discriminant = round_length.powf(2.0) - 4.0 * record;
-    let upper = round_length + discriminant.sqrt() / 2.0;
-    let lower = round_length - discriminant.sqrt() / 2.0;
+//
+// Returns `None` if the discriminant doesn't fit in a u128
+fn shortest_hold_estimate(time: u128, record: u128) -> Option<u128> {
+    let discriminant = time
+        .checked_mul(time)?
+        .checked_sub(record.checked_mul(4)?)?;
+
+    Some((time - discriminant.isqrt()) / 2)
+}
+
+/// Binary search for the first hold in `low..=high` that beats the record
+fn first_win_by_search(mut low: u128, mut high: u128, beats: impl Fn(u128) -> bool) -> u128 {
+    while low < high {
+        let middle = low + (high - low) / 2;
+        match beats(middle) {
+            true => high = middle,
+            false => low = middle + 1,
+        }
+    }
 
-    upper.floor() - lower.floor()
+    low
 }
+
+fn main() {
+    // let input = include_str!("../../test_input.txt");
+    let input = include_str!("../../input.txt");
+
+    let part1: u128 = Race::parse_all(input)
+        .into_iter()
+        .map(Race::ways_to_win)
+        .product();
+    println!("part1 = {part1}");
+
+    let part2 = Race::parse_kerned(input).ways_to_win();
+    println!("part2 = {part2}");
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use super::Race;
+
+const TEST_INPUT: &str = include_str!("../../test_input.txt");
+
+/// Brute force: try every possible hold time
+fn attempt(round_length: u64, record: u64) -> usize {
+    (1..round_length)
+        .map(|speed| speed * (round_length - speed))
+        .filter(|distance| *distance > record)
+        .count()
+}
+
+fn ways_to_win(time: u128, record: u128) -> u128 {
+    Race { time, record }.ways_to_win()
+}
+
+#[test]
+fn example() {
+    let races = Race::parse_all(TEST_INPUT);
+    let ways: Vec<u128> = races.into_iter().map(Race::ways_to_win).collect();
+    assert_eq!(vec![4, 8, 9], ways);
+
+    let race = Race::parse_kerned(TEST_INPUT);
+    assert_eq!(
+        Race {
+            time: 71530,
+            record: 940200
+        },
+        race
+    );
+    assert_eq!(71503, race.ways_to_win());
+}
+
+#[test]
+fn matches_brute_force() {
+    for time in 0..60u64 {
+        for record in 0..(time * time / 4 + 2) {
+            assert_eq!(
+                attempt(time, record) as u128,
+                ways_to_win(time.into(), record.into()),
+                "time = {time}, record = {record}"
+            );
+        }
+    }
+}
+
+#[test]
+fn exact_at_u64_boundaries() {
+    let time = u64::MAX as u128;
+    let half = time / 2;
+    let best = half * (time - half);
+
+    assert_eq!(0, ways_to_win(time, best));
+    assert_eq!(2, ways_to_win(time, best - 1));
+
+    // Just below and just above the distance from holding for one millisecond
+    assert_eq!(time - 1, ways_to_win(time, time - 2));
+    assert_eq!(time - 3, ways_to_win(time, time - 1));
+}
+
+#[test]
+fn exact_at_u128_boundaries() {
+    // Far too big to square so this has to fall back to a search
+    let time = u128::MAX;
+    assert_eq!(time - 1, ways_to_win(time, 0));
+    assert_eq!(time - 3, ways_to_win(time, time - 1));
+    assert_eq!(time - 3, ways_to_win(time, u128::MAX));
+
+    // Holding for 1000 would only tie the record
+    let time = u128::MAX / 2000;
+    let record = 1000 * (time - 1000);
+    assert_eq!(time - 2 * 1001 + 1, ways_to_win(time, record));
+}
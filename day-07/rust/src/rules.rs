@@ -0,0 +1,154 @@
+/// How to break ties between hands of the same type
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare the first card, then the second, and so on
+    CardOrder,
+    /// Compare the highest card, then the next highest, and so on
+    SortedRanks,
+}
+
+impl TieBreak {
+    pub fn parse(string: &str) -> Self {
+        match string {
+            "order" => Self::CardOrder,
+            "sorted" => Self::SortedRanks,
+            _ => panic!("unknown tie break: {string} (expected order or sorted)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rules {
+    /// Every card, weakest first
+    pub order: Vec<char>,
+    /// Cards that pretend to be whatever makes the hand strongest
+    pub wild: Vec<char>,
+    pub hand_size: usize,
+    pub tie_break: TieBreak,
+}
+
+impl Rules {
+    pub fn standard() -> Self {
+        Self {
+            order: "23456789TJQKA".chars().collect(),
+            wild: vec![],
+            hand_size: 5,
+            tie_break: TieBreak::CardOrder,
+        }
+    }
+
+    pub fn jokers() -> Self {
+        Self {
+            order: "J23456789TQKA".chars().collect(),
+            wild: vec!['J'],
+            ..Self::standard()
+        }
+    }
+
+    /// Precomputes everything needed to score hands quickly
+    pub fn scorer(&self) -> Scorer {
+        assert!(self.order.len() <= 256, "too many different cards");
+        assert!(
+            (1..=MAX_HAND_SIZE).contains(&self.hand_size),
+            "bad hand size"
+        );
+
+        let mut ranks = [None; 128];
+        let mut wild = [false; 128];
+        for (rank, &card) in self.order.iter().enumerate() {
+            assert!(card.is_ascii(), "cards must be ASCII: {card}");
+            ranks[card as usize] = Some(rank as u8);
+        }
+        for &card in &self.wild {
+            assert!(
+                ranks.get(card as usize).copied().flatten().is_some(),
+                "wild card {card} isn't in the card order"
+            );
+            wild[card as usize] = true;
+        }
+
+        let bits = |max: usize| usize::BITS - max.leading_zeros();
+        let count_bits = bits(self.hand_size);
+        let rank_bits = bits(self.order.len().saturating_sub(1)).max(1);
+        assert!(
+            (count_bits + rank_bits) as usize * self.hand_size <= u128::BITS as usize,
+            "hands are too big to pack into a u128"
+        );
+
+        Scorer {
+            ranks,
+            wild,
+            hand_size: self.hand_size,
+            tie_break: self.tie_break,
+            count_bits,
+            rank_bits,
+        }
+    }
+}
+
+const MAX_HAND_SIZE: usize = 64;
+
+/// Turns a hand into a single number that sorts the same way the hand does
+#[derive(Debug, Clone)]
+pub struct Scorer {
+    ranks: [Option<u8>; 128],
+    wild: [bool; 128],
+    hand_size: usize,
+    tie_break: TieBreak,
+    count_bits: u32,
+    rank_bits: u32,
+}
+
+impl Scorer {
+    fn rank(&self, card: u8) -> u8 {
+        let rank = self.ranks.get(card as usize).copied().flatten();
+        rank.unwrap_or_else(|| panic!("bad input: {}", card as char))
+    }
+
+    /// The hand's type in the high bits followed by its cards in the low bits.
+    ///
+    /// The type is how many of each card the hand has, biggest group first.
+    /// Comparing those gives the same ordering as five of a kind, four of a
+    /// kind, full house, etc. for any hand size.
+    pub fn key(&self, hand: &str) -> u128 {
+        let hand = hand.as_bytes();
+        assert_eq!(self.hand_size, hand.len(), "bad hand size");
+
+        let mut counts = [0u8; 256];
+        let mut wild = 0;
+        let mut ranks = [0u8; MAX_HAND_SIZE];
+
+        for (i, &card) in hand.iter().enumerate() {
+            let rank = self.rank(card);
+            ranks[i] = rank;
+            match self.wild[card as usize] {
+                true => wild += 1,
+                false => counts[rank as usize] += 1,
+            }
+        }
+
+        let mut signature = [0u8; MAX_HAND_SIZE];
+        let groups = counts.iter().filter(|&&count| count > 0);
+        for (slot, &count) in signature.iter_mut().zip(groups) {
+            *slot = count;
+        }
+        let signature = &mut signature[..self.hand_size];
+        signature.sort_unstable_by(|a, b| b.cmp(a));
+
+        // Wild cards always do best by joining the biggest group
+        signature[0] += wild;
+
+        let ranks = &mut ranks[..self.hand_size];
+        if self.tie_break == TieBreak::SortedRanks {
+            ranks.sort_unstable_by(|a, b| b.cmp(a));
+        }
+
+        let key = signature.iter().fold(0u128, |key, &count| {
+            (key << self.count_bits) | count as u128
+        });
+
+        ranks
+            .iter()
+            .fold(key, |key, &rank| (key << self.rank_bits) | rank as u128)
+    }
+}
@@ -0,0 +1,226 @@
+use super::rules::{Rules, TieBreak};
+use super::winnings;
+
+fn custom(order: &str, wild: &str, hand_size: usize) -> Rules {
+    Rules {
+        order: order.chars().collect(),
+        wild: wild.chars().collect(),
+        hand_size,
+        tie_break: TieBreak::CardOrder,
+    }
+}
+
+/// Checks that each hand beats the one before it
+fn assert_increasing(rules: &Rules, hands: &[&str]) {
+    let scorer = rules.scorer();
+    for pair in hands.windows(2) {
+        assert!(
+            scorer.key(pair[0]) < scorer.key(pair[1]),
+            "{} should lose to {}",
+            pair[0],
+            pair[1]
+        );
+    }
+}
+
+#[test]
+fn example() {
+    let input = include_str!("../../test_input.txt");
+
+    assert_eq!(winnings(input, &Rules::standard()), 6440);
+    assert_eq!(winnings(input, &Rules::jokers()), 5905);
+}
+
+#[test]
+fn signature_order() {
+    // The same order as the old table of types, from high card up to five of
+    // a kind, with the cards getting worse as the types get better so only
+    // the type can be what puts them in order
+    let types = [
+        "AKQT9", "QQ234", "TT993", "77742", "66655", "44443", "22222",
+    ];
+
+    assert_increasing(&Rules::standard(), &types);
+}
+
+#[test]
+fn same_type_compares_cards_in_order() {
+    assert_increasing(&Rules::standard(), &["2AAAA", "33332"]);
+    assert_increasing(&Rules::standard(), &["KTJJT", "KK677"]);
+    assert_increasing(&Rules::standard(), &["T55J5", "QQQJA"]);
+}
+
+#[test]
+fn other_hand_sizes() {
+    let rules = custom("23456789TJQKA", "", 3);
+    assert_increasing(&rules, &["AK2", "AKQ", "22A", "AAK", "222"]);
+    assert_eq!(winnings("AAK 10\n222 1\nAK2 100", &rules), 100 + 2 * 10 + 3);
+
+    // Two threes of a kind still lose to a four of a kind with 7 cards
+    let rules = custom("23456789TJQKA", "", 7);
+    assert_increasing(&rules, &["AAKKQQ2", "AAAKKKQ", "2222AKQ", "2222333"]);
+
+    // With one card there's only the card itself to go on
+    let rules = custom("23456789TJQKA", "", 1);
+    assert_increasing(&rules, &["2", "9", "A"]);
+}
+
+#[test]
+#[should_panic(expected = "bad hand size")]
+fn wrong_hand_size() {
+    Rules::standard().scorer().key("AAKK");
+}
+
+#[test]
+fn several_wild_ranks() {
+    let rules = custom("23456789TJQKA", "2J", 5);
+
+    // 2 and J both join the biggest group, and still rank where they are
+    assert_increasing(&rules, &["AKQT9", "2KQT9", "2JQT9", "33345", "2J2J3"]);
+    assert_increasing(&rules, &["AAAAK", "2JQQQ", "JJQQQ", "QQQQQ"]);
+}
+
+#[test]
+fn all_wild() {
+    let rules = Rules::jokers();
+
+    // Five jokers are five of a kind, but the weakest one
+    assert_increasing(&rules, &["AAAAK", "JJJJJ", "JJJJ2", "22222"]);
+}
+
+#[test]
+fn sorted_ranks() {
+    let rules = Rules {
+        tie_break: TieBreak::SortedRanks,
+        ..Rules::standard()
+    };
+    let sorted = rules.scorer();
+    let in_order = Rules::standard().scorer();
+
+    // Highest card first, wherever it is in the hand
+    assert!(in_order.key("KQJT8") > in_order.key("2A345"));
+    assert!(sorted.key("KQJT8") < sorted.key("2A345"));
+
+    // The same cards in any order are a tie
+    assert_eq!(sorted.key("23AKQ"), sorted.key("AKQ32"));
+    assert_ne!(in_order.key("23AKQ"), in_order.key("AKQ32"));
+
+    // The type still comes first
+    assert!(sorted.key("AKQT9") < sorted.key("22345"));
+    assert!(sorted.key("KK2AA") < sorted.key("AAKK3"));
+}
+
+/// The type the slow way: how many of each card there are, biggest group
+/// first, after turning every wild card into whichever card does best
+fn reference_type(rules: &Rules, hand: &str) -> Vec<usize> {
+    rules
+        .order
+        .iter()
+        .map(|&replacement| {
+            let hand: Vec<char> = hand
+                .chars()
+                .map(|card| match rules.wild.contains(&card) {
+                    true => replacement,
+                    false => card,
+                })
+                .collect();
+
+            let mut counts: Vec<usize> = rules
+                .order
+                .iter()
+                .map(|card| hand.iter().filter(|&c| c == card).count())
+                .filter(|&count| count > 0)
+                .collect();
+            counts.sort_unstable_by(|a, b| b.cmp(a));
+            counts
+        })
+        .max()
+        .unwrap()
+}
+
+fn reference_ranks(rules: &Rules, hand: &str) -> Vec<usize> {
+    let rank = |card| rules.order.iter().position(|&c| c == card).unwrap();
+    let mut ranks: Vec<usize> = hand.chars().map(rank).collect();
+    if rules.tie_break == TieBreak::SortedRanks {
+        ranks.sort_unstable_by(|a, b| b.cmp(a));
+    }
+    ranks
+}
+
+/// Compares hands by type and then by their cards
+fn reference_key(rules: &Rules, hand: &str) -> (Vec<usize>, Vec<usize>) {
+    (reference_type(rules, hand), reference_ranks(rules, hand))
+}
+
+/// xorshift64, just to get a reproducible spread of hands
+fn random_hands(rules: &Rules, count: usize, mut seed: u64) -> Vec<String> {
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed as usize
+    };
+
+    (0..count)
+        .map(|i| {
+            // Half of the hands only use a few different cards so there are
+            // plenty of big groups and ties
+            let cards = match i % 2 {
+                0 => rules.order.len(),
+                _ => rules.order.len().min(3),
+            };
+            let offset = next() % (rules.order.len() - cards + 1);
+            (0..rules.hand_size)
+                .map(|_| rules.order[offset + next() % cards])
+                .collect()
+        })
+        .collect()
+}
+
+fn assert_matches_reference(rules: &Rules) {
+    let scorer = rules.scorer();
+    let mut hands: Vec<_> = random_hands(rules, 2000, 0x5eed_cafe_f00d_d00d)
+        .into_iter()
+        .map(|hand| (reference_key(rules, &hand), scorer.key(&hand), hand))
+        .collect();
+    hands.sort();
+
+    // Sorted by the reference so it's enough to check neighbors agree
+    for pair in hands.windows(2) {
+        let ((expected_a, key_a, a), (expected_b, key_b, b)) = (&pair[0], &pair[1]);
+        assert_eq!(
+            key_a.cmp(key_b),
+            expected_a.cmp(expected_b),
+            "{a} vs {b} with {rules:?}"
+        );
+    }
+}
+
+#[test]
+fn key_matches_reference() {
+    assert_matches_reference(&Rules::standard());
+    assert_matches_reference(&Rules::jokers());
+    assert_matches_reference(&custom("23456789TJQKA", "2J", 5));
+    assert_matches_reference(&Rules {
+        tie_break: TieBreak::SortedRanks,
+        ..Rules::jokers()
+    });
+    assert_matches_reference(&custom("abcdefg", "a", 7));
+    assert_matches_reference(&custom("xyz", "", 3));
+}
+
+#[test]
+fn biggest_hand_that_packs() {
+    // 13 cards take 4 bits and counts up to 15 take 4 bits, so 15 cards use
+    // 120 of the 128 bits
+    let rules = custom("23456789TJQKA", "J", 15);
+    rules.scorer();
+    assert_matches_reference(&rules);
+}
+
+#[test]
+#[should_panic(expected = "hands are too big to pack into a u128")]
+fn hand_too_big_to_pack() {
+    // Counts up to 16 need 5 bits, so 16 cards need 144 bits
+    custom("23456789TJQKA", "", 16).scorer();
+}
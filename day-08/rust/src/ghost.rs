@@ -0,0 +1,153 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{Direction, Graph};
+
+/// Everything about where a ghost goes, boiled down to a lasso: a tail that's
+/// only walked once followed by a cycle that repeats forever.
+#[derive(Debug, Clone)]
+pub struct Ghost<'a> {
+    pub start: &'a str,
+    /// Steps taken before the ghost enters its cycle
+    pub tail: u64,
+    /// Steps it takes to go around the cycle once
+    pub cycle: u64,
+    /// Every step (less than `tail + cycle`) at which the ghost is on a Z node
+    pub hits: Vec<u64>,
+}
+
+impl<'a> Ghost<'a> {
+    pub fn trace(start: &'a str, directions: &[Direction], graph: &Graph<'a>) -> Self {
+        assert!(
+            !directions.is_empty(),
+            "a ghost can't go anywhere without any directions"
+        );
+
+        // The ghost's state is where it is *and* where it is in the directions
+        let mut seen: HashMap<(&str, usize), u64> = HashMap::new();
+        let mut hits = vec![];
+        let mut node = start;
+        let mut step = 0;
+
+        loop {
+            let position = step as usize % directions.len();
+            if let Some(&first_seen) = seen.get(&(node, position)) {
+                return Self {
+                    start,
+                    tail: first_seen,
+                    cycle: step - first_seen,
+                    hits,
+                };
+            }
+
+            seen.insert((node, position), step);
+            if node.ends_with('Z') {
+                hits.push(step);
+            }
+
+            let (left, right) = graph[node];
+            node = match directions[position] {
+                Direction::Left => left,
+                Direction::Right => right,
+            };
+            step += 1;
+        }
+    }
+
+    /// Whether the ghost is on a Z node after `step` steps
+    fn hits_at(&self, step: u64) -> bool {
+        let step = match step < self.tail {
+            true => step,
+            false => self.tail + (step - self.tail) % self.cycle,
+        };
+
+        self.hits.binary_search(&step).is_ok()
+    }
+
+    /// The hits that come around again every cycle, as residues mod `cycle`
+    fn cycle_residues(&self) -> impl Iterator<Item = u64> + '_ {
+        self.hits
+            .iter()
+            .filter(|&&hit| hit >= self.tail)
+            .map(|hit| hit % self.cycle)
+    }
+}
+
+/// The first step at which every ghost is on a Z node at the same time
+pub fn first_meeting(ghosts: &[Ghost]) -> Option<u64> {
+    let longest_tail = ghosts.iter().map(|ghost| ghost.tail).max()?;
+
+    // Before every ghost has made it into its cycle we have to check by hand
+    let early = (0..longest_tail).find(|&step| ghosts.iter().all(|ghost| ghost.hits_at(step)));
+    if early.is_some() {
+        return early;
+    }
+
+    // After that every ghost is going around its cycle so each one is on a
+    // Z node exactly when the step is one of a few residues mod its cycle
+    let mut solutions: BTreeSet<(u128, u128)> = BTreeSet::from([(0, 1)]);
+    for ghost in ghosts {
+        solutions = solutions
+            .into_iter()
+            .flat_map(|(residue, modulus)| {
+                ghost.cycle_residues().filter_map(move |hit| {
+                    crt((residue, modulus), (hit.into(), ghost.cycle.into()))
+                })
+            })
+            .collect();
+    }
+
+    solutions
+        .into_iter()
+        .map(|(residue, modulus)| {
+            // The smallest step that's at least `longest_tail` and ≡ residue
+            let longest_tail = u128::from(longest_tail);
+            let behind = (residue + modulus - longest_tail % modulus) % modulus;
+            longest_tail + behind
+        })
+        .min()
+        .and_then(|step| u64::try_from(step).ok())
+}
+
+/// Generalized Chinese remainder theorem: finds x with x ≡ a (mod n) and
+/// x ≡ b (mod m), even if n and m aren't coprime
+pub(crate) fn crt((a, n): (u128, u128), (b, m): (u128, u128)) -> Option<(u128, u128)> {
+    let g = gcd(n, m);
+    let (a, b) = (a % n, b % m);
+
+    // a + n * k ≡ b (mod m)  →  (n / g) * k ≡ (b - a) / g (mod m / g)
+    let difference = (b + m - a % m) % m;
+    if difference % g != 0 {
+        return None;
+    }
+
+    let m_g = m / g;
+    let k = (difference / g) % m_g * mod_inverse((n / g) % m_g, m_g) % m_g;
+    let lcm = n / g * m;
+
+    Some(((a + n * k) % lcm, lcm))
+}
+
+/// Returns x with a * x ≡ 1 (mod m) using the extended Euclidean algorithm
+fn mod_inverse(a: u128, m: u128) -> u128 {
+    if m == 1 {
+        return 0;
+    }
+
+    let (mut old_r, mut r) = (a as i128, m as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+
+    old_s.rem_euclid(m as i128) as u128
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
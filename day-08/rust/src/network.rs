@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
+
+use crate::ghost::Ghost;
+use crate::{Direction, Graph};
+
+const CYCLE_COLORS: [&str; 6] = ["red", "blue", "darkgreen", "orange", "purple", "brown"];
+
+/// Every node that can be reached from `start` by any mix of lefts and rights
+pub fn reachable<'a>(start: &'a str, graph: &Graph<'a>) -> BTreeSet<&'a str> {
+    let mut seen = BTreeSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let (left, right) = graph[node];
+        for next in [left, right] {
+            if seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Nodes that lead nowhere but back to themselves
+pub fn dead_ends<'a>(graph: &Graph<'a>) -> BTreeSet<&'a str> {
+    graph
+        .iter()
+        .filter(|&(node, &(left, right))| left == *node && right == *node)
+        .map(|(&node, _)| node)
+        .collect()
+}
+
+/// The edges a ghost keeps walking around forever
+pub fn cycle_edges<'a>(
+    ghost: &Ghost<'a>,
+    directions: &[Direction],
+    graph: &Graph<'a>,
+) -> BTreeSet<(&'a str, &'a str)> {
+    let mut edges = BTreeSet::new();
+    let mut node = ghost.start;
+
+    for step in 0..ghost.tail + ghost.cycle {
+        let (left, right) = graph[node];
+        let next = match directions[step as usize % directions.len()] {
+            Direction::Left => left,
+            Direction::Right => right,
+        };
+
+        if step >= ghost.tail {
+            edges.insert((node, next));
+        }
+        node = next;
+    }
+
+    edges
+}
+
+/// A plain-text report on the shape of the network and every ghost's path
+/// through it, including whether the LCM shortcut for part 2 would hold up.
+pub fn analyze(ghosts: &[Ghost], directions: &[Direction], graph: &Graph) -> String {
+    let mut out = String::new();
+    let mut reached_by_any = BTreeSet::new();
+
+    writeln!(
+        out,
+        "{} nodes, {} directions",
+        graph.len(),
+        directions.len()
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+
+    for ghost in ghosts {
+        let reached = reachable(ghost.start, graph);
+        let zs: Vec<&str> = reached
+            .iter()
+            .copied()
+            .filter(|node| node.ends_with('Z'))
+            .collect();
+        let cycle_nodes: BTreeSet<&str> = cycle_edges(ghost, directions, graph)
+            .into_iter()
+            .map(|(from, _)| from)
+            .collect();
+
+        // The LCM shortcut needs exactly one Z per cycle, right at the end
+        let lcm_friendly = ghost.hits == [ghost.cycle];
+
+        writeln!(out, "{}:", ghost.start).unwrap();
+        writeln!(out, "  reachable nodes: {}", reached.len()).unwrap();
+        writeln!(out, "  reachable Z nodes: {}", zs.join(", ")).unwrap();
+        writeln!(out, "  tail: {} steps", ghost.tail).unwrap();
+        writeln!(
+            out,
+            "  cycle: {} steps over {} nodes ({} laps of the directions)",
+            ghost.cycle,
+            cycle_nodes.len(),
+            ghost.cycle as f64 / directions.len() as f64
+        )
+        .unwrap();
+        writeln!(out, "  Z hits at: {:?}", ghost.hits).unwrap();
+        writeln!(out, "  LCM shortcut holds: {lcm_friendly}").unwrap();
+
+        reached_by_any.extend(reached);
+    }
+
+    let mut unreachable: Vec<&str> = graph
+        .keys()
+        .copied()
+        .filter(|node| !reached_by_any.contains(node))
+        .collect();
+    unreachable.sort();
+
+    writeln!(out).unwrap();
+    writeln!(out, "unreachable from any A node: {}", list(&unreachable)).unwrap();
+    let dead_ends: Vec<&str> = dead_ends(graph).into_iter().collect();
+    writeln!(out, "dead ends: {}", list(&dead_ends)).unwrap();
+
+    out
+}
+
+fn list(nodes: &[&str]) -> String {
+    match nodes.is_empty() {
+        true => String::from("none"),
+        false => nodes.join(", "),
+    }
+}
+
+/// Renders the network for Graphviz with each ghost's cycle in its own color
+pub fn dot(ghosts: &[Ghost], directions: &[Direction], graph: &Graph) -> String {
+    let mut out = String::from("digraph network {\n");
+    out.push_str("  node [shape=circle, fontname=monospace];\n");
+
+    let mut nodes: Vec<&str> = graph.keys().copied().collect();
+    nodes.sort();
+
+    for &node in &nodes {
+        let style = match (node.ends_with('A'), node.ends_with('Z')) {
+            (true, _) => " [style=filled, fillcolor=palegreen]",
+            (_, true) => " [style=filled, fillcolor=lightpink]",
+            _ => "",
+        };
+        writeln!(out, "  \"{node}\"{style};").unwrap();
+    }
+
+    // Which cycles (by color) each edge is on
+    let mut colors: BTreeMap<(&str, &str), Vec<&str>> = BTreeMap::new();
+    for (ghost, color) in ghosts.iter().zip(CYCLE_COLORS.iter().cycle()) {
+        for edge in cycle_edges(ghost, directions, graph) {
+            colors.entry(edge).or_default().push(color);
+        }
+    }
+
+    for &node in &nodes {
+        let (left, right) = graph[node];
+        let edges: &[(&str, &str)] = match left == right {
+            true => &[(left, "LR")],
+            false => &[(left, "L"), (right, "R")],
+        };
+
+        for &(next, label) in edges {
+            let style = match colors.get(&(node, next)) {
+                Some(colors) => format!(", color=\"{}\", penwidth=2", colors.join(":")),
+                None => String::new(),
+            };
+            writeln!(out, "  \"{node}\" -> \"{next}\" [label={label}{style}];").unwrap();
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
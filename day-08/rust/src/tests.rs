@@ -0,0 +1,241 @@
+use super::ghost::{crt, first_meeting, Ghost};
+use super::network;
+use super::{parse, Direction, Graph};
+
+/// Walks every ghost one step at a time, the slow but obviously right way
+fn brute_force(
+    starts: &[&str],
+    directions: &[Direction],
+    graph: &Graph,
+    limit: u64,
+) -> Option<u64> {
+    let mut nodes: Vec<&str> = starts.to_vec();
+
+    for step in 0..limit {
+        if nodes.iter().all(|node| node.ends_with('Z')) {
+            return Some(step);
+        }
+
+        let direction = directions[step as usize % directions.len()];
+        for node in &mut nodes {
+            let (left, right) = graph[*node];
+            *node = match direction {
+                Direction::Left => left,
+                Direction::Right => right,
+            };
+        }
+    }
+
+    None
+}
+
+/// Traces every ghost in `starts`, checks `first_meeting` against walking
+/// them by hand, and returns it
+fn meeting(input: &str, starts: &[&str]) -> Option<u64> {
+    let (directions, graph) = parse(input);
+    let ghosts: Vec<Ghost> = starts
+        .iter()
+        .map(|start| Ghost::trace(start, &directions, &graph))
+        .collect();
+
+    let meeting = first_meeting(&ghosts);
+    assert_eq!(meeting, brute_force(starts, &directions, &graph, 1_000));
+    meeting
+}
+
+#[test]
+fn example() {
+    assert_eq!(
+        meeting(include_str!("../../test_input3.txt"), &["11A", "22A"]),
+        Some(6)
+    );
+}
+
+const TAIL: &str = "\
+L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11C, 11C)
+11C = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22A, 22A)
+33A = (33B, 33B)
+33B = (33Z, 33Z)
+33Z = (33B, 33B)";
+
+#[test]
+fn hit_inside_the_tail() {
+    let (directions, graph) = parse(TAIL);
+    let ghost = Ghost::trace("11A", &directions, &graph);
+
+    assert_eq!((ghost.tail, ghost.cycle), (2, 2));
+    assert_eq!(ghost.hits, [1]);
+
+    assert_eq!(meeting(TAIL, &["11A"]), Some(1));
+    assert_eq!(meeting(TAIL, &["11A", "22A"]), Some(1));
+}
+
+#[test]
+fn never_meet() {
+    // 11A is only ever on a Z node at step 1 and 33A only at even steps
+    assert_eq!(meeting(TAIL, &["11A", "33A"]), None);
+}
+
+const SEVERAL_HITS: &str = "\
+RL
+
+44A = (41Z, 41Z)
+41Z = (44B, 44B)
+44B = (42Z, 42Z)
+42Z = (44C, 44C)
+44C = (44A, 44A)
+55A = (55B, 55B)
+55B = (55Z, 55Z)
+55Z = (55A, 55A)";
+
+#[test]
+fn several_hits_per_cycle() {
+    let (directions, graph) = parse(SEVERAL_HITS);
+    let ghost = Ghost::trace("44A", &directions, &graph);
+
+    // The directions only line up with the nodes again after two laps
+    assert_eq!((ghost.tail, ghost.cycle), (0, 10));
+    assert_eq!(ghost.hits, [1, 3, 6, 8]);
+
+    // 1 or 3 mod 5 and 2 mod 3
+    assert_eq!(meeting(SEVERAL_HITS, &["44A", "55A"]), Some(8));
+}
+
+const NOT_COPRIME: &str = "\
+L
+
+66A = (66B, 66B)
+66B = (66C, 66C)
+66C = (66Z, 66Z)
+66Z = (66A, 66A)
+77A = (77B, 77B)
+77B = (77C, 77C)
+77C = (77D, 77D)
+77D = (77E, 77E)
+77E = (77Z, 77Z)
+77Z = (77A, 77A)
+88A = (88B, 88B)
+88B = (88C, 88C)
+88C = (88Z, 88Z)
+88Z = (88D, 88D)
+88D = (88E, 88E)
+88E = (88A, 88A)";
+
+#[test]
+fn cycles_that_are_not_coprime() {
+    // 3 mod 4 and 5 mod 6
+    assert_eq!(meeting(NOT_COPRIME, &["66A", "77A"]), Some(11));
+    // 3 mod 4 and 3 mod 6 only agree at 3 mod 12
+    assert_eq!(meeting(NOT_COPRIME, &["66A", "88A"]), Some(3));
+    // 5 mod 6 and 3 mod 6 never agree
+    assert_eq!(meeting(NOT_COPRIME, &["77A", "88A"]), None);
+}
+
+#[test]
+#[should_panic(expected = "without any directions")]
+fn no_directions() {
+    let (_, graph) = parse(TAIL);
+    Ghost::trace("11A", &[], &graph);
+}
+
+#[test]
+fn crt_coprime() {
+    assert_eq!(crt((2, 3), (3, 5)), Some((8, 15)));
+    assert_eq!(crt((0, 7), (0, 11)), Some((0, 77)));
+    assert_eq!(crt((5, 1), (4, 9)), Some((4, 9)));
+}
+
+#[test]
+fn crt_not_coprime() {
+    assert_eq!(crt((3, 4), (5, 6)), Some((11, 12)));
+    assert_eq!(crt((2, 4), (3, 6)), None);
+    assert_eq!(crt((4, 6), (4, 6)), Some((4, 6)));
+}
+
+#[test]
+fn crt_large_moduli() {
+    let (n, m) = (1_000_000_007, 998_244_353);
+    let (x, lcm) = crt((123, n), (456, m)).unwrap();
+
+    assert_eq!(lcm, n * m);
+    assert_eq!((x % n, x % m), (123, 456));
+}
+
+fn example_ghosts<'a>(directions: &[Direction], graph: &Graph<'a>) -> Vec<Ghost<'a>> {
+    ["11A", "22A"]
+        .into_iter()
+        .map(|start| Ghost::trace(start, directions, graph))
+        .collect()
+}
+
+#[test]
+fn example_ghost_paths() {
+    let (directions, graph) = parse(include_str!("../../test_input3.txt"));
+    let ghosts = example_ghosts(&directions, &graph);
+
+    assert_eq!((ghosts[0].tail, ghosts[0].cycle), (1, 2));
+    assert_eq!(ghosts[0].hits, [2]);
+    assert_eq!((ghosts[1].tail, ghosts[1].cycle), (1, 6));
+    assert_eq!(ghosts[1].hits, [3, 6]);
+}
+
+#[test]
+fn example_dead_ends() {
+    let (_, graph) = parse(include_str!("../../test_input3.txt"));
+
+    assert_eq!(
+        network::dead_ends(&graph).into_iter().collect::<Vec<_>>(),
+        ["XXX"]
+    );
+    assert_eq!(
+        network::reachable("22A", &graph)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        ["22A", "22B", "22C", "22Z", "XXX"]
+    );
+}
+
+#[test]
+fn example_analysis() {
+    let (directions, graph) = parse(include_str!("../../test_input3.txt"));
+    let ghosts = example_ghosts(&directions, &graph);
+    let report = network::analyze(&ghosts, &directions, &graph);
+
+    assert!(report.starts_with("8 nodes, 2 directions\n"));
+    assert!(report.contains(
+        "22A:\n  \
+         reachable nodes: 5\n  \
+         reachable Z nodes: 22Z\n  \
+         tail: 1 steps\n  \
+         cycle: 6 steps over 3 nodes (3 laps of the directions)\n  \
+         Z hits at: [3, 6]\n  \
+         LCM shortcut holds: false\n"
+    ));
+    assert!(report.contains("unreachable from any A node: none\n"));
+    assert!(report.ends_with("dead ends: XXX\n"));
+}
+
+#[test]
+fn example_dot() {
+    let (directions, graph) = parse(include_str!("../../test_input3.txt"));
+    let ghosts = example_ghosts(&directions, &graph);
+    let dot = network::dot(&ghosts, &directions, &graph);
+
+    assert!(dot.starts_with("digraph network {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("  \"11A\" [style=filled, fillcolor=palegreen];\n"));
+    assert!(dot.contains("  \"22Z\" [style=filled, fillcolor=lightpink];\n"));
+    assert!(dot.contains("  \"XXX\";\n"));
+
+    // Only the edges either ghost keeps walking round are colored
+    assert!(dot.contains("  \"11B\" -> \"11Z\" [label=R, color=\"red\", penwidth=2];\n"));
+    assert!(dot.contains("  \"22C\" -> \"22Z\" [label=LR, color=\"blue\", penwidth=2];\n"));
+    assert!(dot.contains("  \"11A\" -> \"11B\" [label=L];\n"));
+    assert!(dot.contains("  \"XXX\" -> \"XXX\" [label=LR];\n"));
+}
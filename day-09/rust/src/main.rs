@@ -1,29 +1,61 @@
+mod bigint;
+mod polynomial;
+use bigint::BigInt;
+use polynomial::Polynomial;
+
 fn main() {
     // let input = include_str!("../../test_input.txt");
     let input = include_str!("../../input.txt");
     let rows: Vec<_> = input.lines().map(parse_row).collect();
 
-    let part1: i64 = rows.iter().map(AsRef::as_ref).map(solve).sum();
-    let part2: i64 = rows.into_iter().map(reverse).map(|nums| solve(&nums)).sum();
+    // Rows that don't fit a polynomial (like a lone number) have no next value
+    // to speak of, so they're left out of every total rather than guessed at
+    let mut fitted: Vec<(usize, &[BigInt], Polynomial)> = vec![];
+    for (i, row) in rows.iter().enumerate() {
+        match Polynomial::fit(row) {
+            Ok(polynomial) => fitted.push((i + 1, row, polynomial)),
+            Err(error) => eprintln!("skipping row {}: {error}", i + 1),
+        }
+    }
+
+    let part1: BigInt = fitted
+        .iter()
+        .map(|(_, row, polynomial)| polynomial.at(row.len() as i64))
+        .sum();
+    let part2: BigInt = fitted
+        .iter()
+        .map(|(_, _, polynomial)| polynomial.at(-1))
+        .sum();
 
     println!("part1 = {part1}");
     println!("part2 = {part2}");
-}
-
-fn solve(row: &[i64]) -> i64 {
-    let next_row: Vec<_> = row.windows(2).map(|nums| nums[1] - nums[0]).collect();
 
-    match next_row.iter().all(|num| *num == 0) {
-        false => row.last().unwrap() + solve(&next_row),
-        true => *row.last().unwrap(),
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--at" => {
+                let x: i64 = args.next().expect("--at needs an index").parse().unwrap();
+                let total: BigInt = fitted
+                    .iter()
+                    .map(|(_, _, polynomial)| polynomial.at(x))
+                    .sum();
+                println!("sum at index {x} = {total}");
+            }
+            "--degrees" => {
+                for (row, _, polynomial) in &fitted {
+                    println!("row {row}: degree {}", polynomial.degree());
+                }
+            }
+            _ => panic!("unknown argument: {arg}"),
+        }
     }
 }
 
-fn parse_row(line: &str) -> Vec<i64> {
-    line.split(' ').map(|num| num.parse().unwrap()).collect()
+fn parse_row(line: &str) -> Vec<BigInt> {
+    line.split_whitespace()
+        .map(|num| num.parse().unwrap())
+        .collect()
 }
 
-fn reverse<T>(mut v: Vec<T>) -> Vec<T> {
-    v.reverse();
-    v
-}
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,144 @@
+use crate::bigint::BigInt;
+
+/// The sequence's differences never settle down to all zeroes so no
+/// polynomial of degree n - 2 or less fits its n values. One of degree n - 1
+/// always fits, but then there's no value left over to check it against.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NotPolynomial;
+
+impl std::fmt::Display for NotPolynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the differences never reach a row of all zeroes")
+    }
+}
+
+/// Almost every sequence fits in an `i128` so that's tried first. Anything
+/// that overflows is redone with big integers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Differences {
+    Small(Vec<i128>),
+    Big(Vec<BigInt>),
+}
+
+/// The one polynomial that passes through every value of a sequence, kept in
+/// Newton's forward difference form:
+///
+/// p(x) = Δ⁰ + Δ¹·C(x, 1) + Δ²·C(x, 2) + ...
+///
+/// where Δⁱ is the first number in the i-th row of differences.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    leading_differences: Differences,
+}
+
+impl Polynomial {
+    /// Builds the difference rows until one is all zeroes
+    pub fn fit(values: &[BigInt]) -> Result<Self, NotPolynomial> {
+        let small: Option<Vec<i128>> = values.iter().map(|v| v.try_into().ok()).collect();
+
+        let leading_differences = match small.map(|values| fit_small(&values)) {
+            Some(Some(result)) => Differences::Small(result?),
+            _ => Differences::Big(fit_big(values)?),
+        };
+
+        Ok(Self {
+            leading_differences,
+        })
+    }
+
+    /// The zero polynomial counts as degree 0 too
+    pub fn degree(&self) -> usize {
+        let terms = match &self.leading_differences {
+            Differences::Small(differences) => differences.len(),
+            Differences::Big(differences) => differences.len(),
+        };
+
+        terms.saturating_sub(1)
+    }
+
+    /// The value at index `x` of the sequence, where 0 is the first value.
+    /// Negative indices go backwards from the start.
+    pub fn at(&self, x: i64) -> BigInt {
+        match &self.leading_differences {
+            Differences::Small(differences) => match at_small(differences, x) {
+                Some(value) => value.into(),
+                None => {
+                    let differences: Vec<BigInt> = differences.iter().map(|&d| d.into()).collect();
+                    at_big(&differences, x)
+                }
+            },
+            Differences::Big(differences) => at_big(differences, x),
+        }
+    }
+}
+
+/// Returns `None` if anything overflows
+fn fit_small(values: &[i128]) -> Option<Result<Vec<i128>, NotPolynomial>> {
+    let mut leading_differences = vec![];
+    let mut row = values.to_vec();
+
+    while !row.is_empty() {
+        if row.iter().all(|&num| num == 0) {
+            return Some(Ok(leading_differences));
+        }
+
+        leading_differences.push(row[0]);
+        row = row
+            .windows(2)
+            .map(|nums| nums[1].checked_sub(nums[0]))
+            .collect::<Option<_>>()?;
+    }
+
+    Some(Err(NotPolynomial))
+}
+
+fn fit_big(values: &[BigInt]) -> Result<Vec<BigInt>, NotPolynomial> {
+    let mut leading_differences = vec![];
+    let mut row = values.to_vec();
+
+    while !row.is_empty() {
+        if row.iter().all(BigInt::is_zero) {
+            return Ok(leading_differences);
+        }
+
+        leading_differences.push(row[0].clone());
+        row = row.windows(2).map(|nums| &nums[1] - &nums[0]).collect();
+    }
+
+    Err(NotPolynomial)
+}
+
+/// Returns `None` if anything overflows
+fn at_small(leading_differences: &[i128], x: i64) -> Option<i128> {
+    let x = i128::from(x);
+    let mut total = 0i128;
+    let mut binomial = 1i128; // C(x, 0)
+
+    for (j, &difference) in leading_differences.iter().enumerate() {
+        if j > 0 {
+            // C(x, j) = C(x, j - 1) * (x - j + 1) / j and always divides evenly
+            let j = j as i128;
+            binomial = binomial.checked_mul(x - j + 1)? / j;
+        }
+        total = total.checked_add(difference.checked_mul(binomial)?)?;
+    }
+
+    Some(total)
+}
+
+fn at_big(leading_differences: &[BigInt], x: i64) -> BigInt {
+    let x = i128::from(x);
+    let mut total = BigInt::default();
+    let mut binomial = BigInt::from(1);
+
+    for (j, difference) in leading_differences.iter().enumerate() {
+        if j > 0 {
+            let factor = BigInt::from(x - j as i128 + 1);
+            let j = u32::try_from(j).expect("sequence is too long");
+            binomial = (&binomial * &factor).div_small(j);
+        }
+        total = &total + &(difference * &binomial);
+    }
+
+    total
+}
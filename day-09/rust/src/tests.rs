@@ -0,0 +1,147 @@
+use crate::bigint::BigInt;
+use crate::parse_row;
+use crate::polynomial::{NotPolynomial, Polynomial};
+
+fn big(string: &str) -> BigInt {
+    string.parse().unwrap()
+}
+
+/// n^power computed directly, without going anywhere near the differences
+fn power(n: i64, power: u32) -> BigInt {
+    let n = BigInt::from(i128::from(n));
+    (0..power).fold(BigInt::from(1), |acc, _| &acc * &n)
+}
+
+#[test]
+fn bigint_round_trips_through_strings() {
+    for string in [
+        "0",
+        "-1",
+        "4294967296",
+        "-170141183460469231731687303715884105728",
+        "123456789012345678901234567890123456789012345678901234567890",
+    ] {
+        assert_eq!(big(string).to_string(), string);
+    }
+
+    assert_eq!(big("-0"), BigInt::default());
+    assert!("12a".parse::<BigInt>().is_err());
+    assert!("-".parse::<BigInt>().is_err());
+}
+
+#[test]
+fn bigint_arithmetic() {
+    let a = big("340282366920938463463374607431768211456"); // 2^128
+    let b = big("-1");
+
+    assert_eq!(
+        (&a + &b).to_string(),
+        "340282366920938463463374607431768211455"
+    );
+    assert_eq!(
+        (&b - &a).to_string(),
+        "-340282366920938463463374607431768211457"
+    );
+    assert_eq!(
+        (&a * &a).to_string(),
+        "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+    );
+    assert_eq!(
+        (&a * &b).div_small(1 << 16).to_string(),
+        "-5192296858534827628530496329220096"
+    );
+    assert!((&a - &a).is_zero());
+}
+
+#[test]
+fn bigint_converts_to_i128_only_when_it_fits() {
+    assert_eq!(i128::try_from(&BigInt::from(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(i128::try_from(&BigInt::from(i128::MAX)), Ok(i128::MAX));
+    assert!(i128::try_from(&(&BigInt::from(i128::MAX) + &BigInt::from(1))).is_err());
+    assert!(i128::try_from(&(&BigInt::from(i128::MIN) - &BigInt::from(1))).is_err());
+}
+
+#[test]
+fn example_still_extrapolates() {
+    let rows = ["0 3 6 9 12 15", "1 3 6 10 15 21", "10 13 16 21 30 45"];
+    let next: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let values = parse_row(row);
+            let polynomial = Polynomial::fit(&values).unwrap();
+            polynomial.at(values.len() as i64).to_string()
+        })
+        .collect();
+
+    assert_eq!(next, ["18", "28", "68"]);
+}
+
+#[test]
+fn high_degree_sequences_overflow_into_big_integers() {
+    // 120 terms of n^30 is far beyond i128 for the later values
+    let values: Vec<BigInt> = (0..120).map(|n| power(n, 30)).collect();
+    let polynomial = Polynomial::fit(&values).unwrap();
+
+    assert_eq!(polynomial.degree(), 30);
+    assert_eq!(polynomial.at(120), power(120, 30));
+    assert_eq!(polynomial.at(500), power(500, 30));
+    assert_eq!(polynomial.at(-7), power(-7, 30));
+}
+
+#[test]
+fn small_values_with_huge_extrapolations_fall_back() {
+    // Fits in i128 but the value at 10^6 doesn't
+    let values: Vec<BigInt> = (0..21).map(|n| power(n, 19)).collect();
+    let polynomial = Polynomial::fit(&values).unwrap();
+
+    assert_eq!(polynomial.at(1_000_000), power(1_000_000, 19));
+}
+
+#[test]
+fn values_too_big_to_parse_as_i128() {
+    let row = "170141183460469231731687303715884105727 \
+               340282366920938463463374607431768211454 \
+               510423550381407695195061911147652317181";
+    let values = parse_row(row);
+    let polynomial = Polynomial::fit(&values).unwrap();
+
+    assert_eq!(polynomial.degree(), 1);
+    assert_eq!(
+        polynomial.at(3).to_string(),
+        "680564733841876926926749214863536422908"
+    );
+    assert_eq!(polynomial.at(-1).to_string(), "0");
+}
+
+#[test]
+fn sequences_that_are_not_polynomials() {
+    // n values always fit something of degree n - 1, so that doesn't count
+    for row in ["5", "1 2 4", "0 0 1", "1 2 4 8 16 32", "3 -3 3 -3"] {
+        assert_eq!(
+            Polynomial::fit(&parse_row(row)),
+            Err(NotPolynomial),
+            "{row}"
+        );
+    }
+    assert_eq!(Polynomial::fit(&[]), Err(NotPolynomial));
+
+    // Big enough that only the big integer path sees it
+    let row = "1 340282366920938463463374607431768211456 \
+               340282366920938463463374607431768211456";
+    assert_eq!(Polynomial::fit(&parse_row(row)), Err(NotPolynomial));
+}
+
+#[test]
+fn shortest_sequences_that_are_polynomials() {
+    let zero = Polynomial::fit(&parse_row("0")).unwrap();
+    assert_eq!((zero.degree(), zero.at(100).to_string()), (0, "0".into()));
+
+    let constant = Polynomial::fit(&parse_row("7 7")).unwrap();
+    assert_eq!(
+        (constant.degree(), constant.at(-5).to_string()),
+        (0, "7".into())
+    );
+
+    let line = Polynomial::fit(&parse_row("1 3 5")).unwrap();
+    assert_eq!((line.degree(), line.at(3).to_string()), (1, "7".into()));
+}
@@ -3,6 +3,8 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::iter::successors;
 
+mod render;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Tile {
     Vertical,
@@ -48,6 +50,21 @@ impl Tile {
             Tile::Start => 'S',
         }
     }
+
+    /// The box-drawing character for the tile, which makes the loop a lot
+    /// easier to follow by eye than `as_char` does
+    fn as_box_char(self) -> char {
+        match self {
+            Tile::Vertical => '│',
+            Tile::Horizontal => '─',
+            Tile::NorthEast => '└',
+            Tile::NorthWest => '┘',
+            Tile::SouthWest => '┐',
+            Tile::SouthEast => '┌',
+            Tile::Ground => ' ',
+            Tile::Start => 'S',
+        }
+    }
 }
 
 fn main() {
@@ -61,37 +78,71 @@ fn main() {
     // let input = include_str!("../../test_input8.txt");
     let input = include_str!("../../input.txt");
 
-    let mut grid: Vec<Vec<Tile>> = input
-        .lines()
-        .map(|line| line.chars().map(Tile::parse).collect::<Vec<Tile>>())
-        .collect();
+    let mut engine = Engine::RayCasting;
+    let mut compare = false;
+    let mut render = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--engine" => engine = Engine::parse(&args.next().expect("--engine needs a name")),
+            "--compare" => compare = true,
+            "--render" => render = true,
+            _ => panic!("unknown argument: {arg}"),
+        }
+    }
 
-    // put ground around the edges so we don't have to bother checking bounds
-    grid.iter_mut().for_each(|row| {
-        row.insert(0, Tile::Ground);
-        row.push(Tile::Ground);
-    });
-
-    grid.insert(0, vec![Tile::Ground; grid.len() + 2]);
-    grid.push(vec![Tile::Ground; grid.len() + 1]);
-
-    let (y, x) = grid
-        .iter()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.iter()
-                .enumerate()
-                .find(|(_, tile)| **tile == Tile::Start)
-                .map(|(x, _)| (y, x))
-        })
-        .unwrap();
+    let (part1, part2) = solve(input, engine);
+    println!("part1 = {part1}");
+    println!("part2 = {part2}");
+
+    if compare {
+        let (_, ray_casting) = solve(input, Engine::RayCasting);
+        let (_, pick) = solve(input, Engine::Pick);
+        println!("ray casting = {ray_casting}, shoelace + pick = {pick}");
+        assert_eq!(ray_casting, pick, "the engines disagree");
+    }
+
+    if render {
+        print!("{}", render::render(input, render::use_color()));
+    }
+}
+
+/// How part 2 counts the tiles enclosed by the loop
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Engine {
+    /// Cast a ray down from every tile and count how many times it crosses
+    /// the loop
+    RayCasting,
+    /// Walk the loop once, take its area with the shoelace formula and get
+    /// the number of tiles inside from Pick's theorem
+    Pick,
+}
+
+impl Engine {
+    fn parse(name: &str) -> Self {
+        match name {
+            "ray" => Self::RayCasting,
+            "pick" => Self::Pick,
+            _ => panic!("unknown engine: {name} (expected ray or pick)"),
+        }
+    }
+}
+
+fn solve(input: &str, engine: Engine) -> (u32, usize) {
+    let grid = Grid::parse(input);
+    let start = grid.find(Tile::Start).expect("there's no S in the input");
+
+    let (part1, pipe) = part1(&grid, start);
+    let part2 = match engine {
+        Engine::RayCasting => part2(grid, &pipe, start).len(),
+        Engine::Pick => part2_pick(&grid, start),
+    };
 
-    let start = Point { y, x };
-    let pipe = part1(&grid, start);
-    part2(grid, pipe, start);
+    (part1, part2)
 }
 
-fn part1(grid: &Grid, start: Point) -> HashSet<Point> {
+fn part1(grid: &Grid, start: Point) -> (u32, HashSet<Point>) {
     // Possible first steps
     let routes = start.reachable_neighbors(grid);
     let mut distances = HashMap::<Point, u32>::from_iter([(start, 0)]);
@@ -120,25 +171,21 @@ fn part1(grid: &Grid, start: Point) -> HashSet<Point> {
     }
 
     let part1: u32 = distances.values().max().copied().unwrap();
-    println!("part1 = {part1}");
 
-    pipe
+    (part1, pipe)
 }
 
-fn part2(mut grid: Grid, pipe: HashSet<Point>, start: Point) {
-    remove_superfluous_tiles(&mut grid, &pipe);
+/// Every tile inside the loop
+fn part2(mut grid: Grid, pipe: &HashSet<Point>, start: Point) -> HashSet<Point> {
+    remove_superfluous_tiles(&mut grid, pipe);
 
     // Replace start with whatever it should be
     let what_to_replace_start_with = start.calculate_start(&grid);
-    let start_tile = grid
-        .iter_mut()
-        .find_map(|row| row.iter_mut().find(|tile| **tile == Tile::Start))
-        .unwrap();
-    *start_tile = what_to_replace_start_with;
+    grid.tiles[start.y][start.x] = what_to_replace_start_with;
 
     let mut inside = HashSet::new();
-    for y in 0..grid.len() {
-        for x in 0..grid.len() {
+    for y in 0..grid.height {
+        for x in 0..grid.width {
             let start = Point { y, x };
             if pipe.contains(&start) {
                 continue;
@@ -176,11 +223,42 @@ fn part2(mut grid: Grid, pipe: HashSet<Point>, start: Point) {
         }
     }
 
-    println!("part2 = {}", inside.len());
+    inside
 }
 
-fn remove_superfluous_tiles(grid: &mut [Vec<Tile>], pipe: &HashSet<Point>) {
-    for (y, row) in grid.iter_mut().enumerate() {
+fn part2_pick(grid: &Grid, start: Point) -> usize {
+    // Straight pipes are just points along an edge so only corners are needed
+    let vertices: Vec<(i64, i64)> = walk_loop(grid, start)
+        .into_iter()
+        .filter(|point| !matches!(point.lookup(grid), Tile::Vertical | Tile::Horizontal))
+        .map(|point| (point.x as i64, point.y as i64))
+        .collect();
+
+    polygon::interior_points(&vertices) as usize
+}
+
+/// The loop's tiles in the order you'd walk them, starting at S
+fn walk_loop(grid: &Grid, start: Point) -> Vec<Point> {
+    let mut tiles = vec![start];
+    let mut came_from = start;
+    let mut current = start.reachable_neighbors(grid).next().unwrap();
+
+    while current != start {
+        tiles.push(current);
+        let next = current
+            .reachable_neighbors(grid)
+            .find(|n| *n != came_from)
+            .unwrap();
+
+        came_from = current;
+        current = next;
+    }
+
+    tiles
+}
+
+fn remove_superfluous_tiles(grid: &mut Grid, pipe: &HashSet<Point>) {
+    for (y, row) in grid.tiles.iter_mut().enumerate() {
         for (x, tile) in row.iter_mut().enumerate() {
             let point = Point { y, x };
             if !pipe.contains(&point) {
@@ -210,11 +288,58 @@ impl std::fmt::Debug for Point {
     }
 }
 
-type Grid = Vec<Vec<Tile>>;
+/// The tiles with a border of ground all the way around so we don't have to
+/// bother checking bounds. `width` and `height` include the border.
+#[derive(Clone)]
+struct Grid {
+    tiles: Vec<Vec<Tile>>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid {
+    fn parse(input: &str) -> Self {
+        let mut tiles: Vec<Vec<Tile>> = input
+            .lines()
+            .map(|line| line.chars().map(Tile::parse).collect())
+            .collect();
+
+        let width = tiles.first().map_or(0, Vec::len);
+        if let Some(y) = tiles.iter().position(|row| row.len() != width) {
+            panic!(
+                "bad input: row {} is {} tiles wide but row 1 is {width}",
+                y + 1,
+                tiles[y].len()
+            );
+        }
+
+        tiles.iter_mut().for_each(|row| {
+            row.insert(0, Tile::Ground);
+            row.push(Tile::Ground);
+        });
+
+        tiles.insert(0, vec![Tile::Ground; width + 2]);
+        tiles.push(vec![Tile::Ground; width + 2]);
+
+        Self {
+            height: tiles.len(),
+            width: width + 2,
+            tiles,
+        }
+    }
+
+    fn find(&self, target: Tile) -> Option<Point> {
+        self.tiles.iter().enumerate().find_map(|(y, row)| {
+            row.iter()
+                .position(|&tile| tile == target)
+                .map(|x| Point { y, x })
+        })
+    }
+}
 
 impl Point {
     fn lookup(&self, grid: &Grid) -> Tile {
-        grid[self.y][self.x]
+        grid.tiles[self.y][self.x]
     }
 
     fn neighbors(&self) -> [Self; 4] {
@@ -289,7 +414,7 @@ impl Point {
 
     fn checked_down(&self, grid: &Grid) -> Option<Self> {
         let y = self.y + 1;
-        match y >= grid.len() {
+        match y >= grid.height {
             true => None,
             false => Some(Self { y, ..*self }),
         }
@@ -346,12 +471,5 @@ enum Direction {
     Right,
 }
 
-#[allow(dead_code)]
-fn debug(grid: &Grid) {
-    for row in grid {
-        for tile in row {
-            print!("{tile}");
-        }
-        println!();
-    }
-}
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,58 @@
+use std::io::IsTerminal;
+
+use crate::{part1, part2, Grid, Point, Tile};
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const START: &str = "\x1b[1;31m";
+const INSIDE: &str = "\x1b[32m";
+
+/// Color only makes sense when a person is looking at the output
+pub fn use_color() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+/// Draws the loop with box-drawing characters, marks the start, and shades
+/// every tile that part 2 counts as inside. Everything outside is dimmed when
+/// there's color to dim it with; otherwise junk pipes keep their original
+/// characters so they can't be mistaken for the loop.
+pub fn render(input: &str, color: bool) -> String {
+    let grid = Grid::parse(input);
+    let start = grid.find(Tile::Start).expect("there's no S in the input");
+    let (_, pipe) = part1(&grid, start);
+    let inside = part2(grid.clone(), &pipe, start);
+
+    let mut out = String::new();
+
+    // Skip the border of ground that parsing added
+    for y in 1..grid.height - 1 {
+        for x in 1..grid.width - 1 {
+            let point = Point { y, x };
+            let tile = point.lookup(&grid);
+
+            let (style, c) = match tile {
+                Tile::Start => (START, 'S'),
+                _ if pipe.contains(&point) => ("", tile.as_box_char()),
+                _ if inside.contains(&point) => (INSIDE, '▒'),
+                Tile::Ground => (DIM, '·'),
+                _ if color => (DIM, tile.as_box_char()),
+                _ => (DIM, tile.as_char()),
+            };
+
+            push_styled(&mut out, c, style, color);
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn push_styled(out: &mut String, c: char, style: &str, color: bool) {
+    if color && !style.is_empty() {
+        out.push_str(style);
+        out.push(c);
+        out.push_str(RESET);
+    } else {
+        out.push(c);
+    }
+}
@@ -0,0 +1,127 @@
+use super::render::render;
+use super::{solve, Engine};
+
+const EXAMPLES: [&str; 8] = [
+    include_str!("../../test_input.txt"),
+    include_str!("../../test_input2.txt"),
+    include_str!("../../test_input3.txt"),
+    include_str!("../../test_input4.txt"),
+    include_str!("../../test_input5.txt"),
+    include_str!("../../test_input6.txt"),
+    include_str!("../../test_input7.txt"),
+    include_str!("../../test_input8.txt"),
+];
+
+fn solve_ray(input: &str) -> (u32, usize) {
+    solve(input, Engine::RayCasting)
+}
+
+#[test]
+fn example_1() {
+    assert_eq!(solve_ray(include_str!("../../test_input.txt")).0, 4);
+}
+
+#[test]
+fn example_2() {
+    assert_eq!(solve_ray(include_str!("../../test_input2.txt")).0, 8);
+}
+
+#[test]
+fn example_3() {
+    assert_eq!(solve_ray(include_str!("../../test_input3.txt")), (23, 4));
+}
+
+#[test]
+fn example_4_squeezes_between_pipes() {
+    assert_eq!(solve_ray(include_str!("../../test_input4.txt")), (22, 4));
+}
+
+#[test]
+fn example_5() {
+    assert_eq!(solve_ray(include_str!("../../test_input5.txt")), (70, 8));
+}
+
+#[test]
+fn example_6() {
+    assert_eq!(solve_ray(include_str!("../../test_input6.txt")), (80, 10));
+}
+
+#[test]
+fn example_7() {
+    assert_eq!(solve_ray(include_str!("../../test_input7.txt")), (16, 29));
+}
+
+#[test]
+fn example_8() {
+    assert_eq!(solve_ray(include_str!("../../test_input8.txt")), (15, 26));
+}
+
+#[test]
+fn wide_grid() {
+    let input = "\
+..........
+.S------7.
+.L------J.";
+    assert_eq!(solve_ray(input), (8, 0));
+}
+
+#[test]
+fn tall_grid() {
+    let input = "\
+.....
+.S-7.
+.|.|.
+.|.|.
+.|.|.
+.L-J.
+.....";
+    assert_eq!(solve_ray(input), (6, 3));
+}
+
+#[test]
+fn engines_agree_on_every_example() {
+    for (i, example) in EXAMPLES.iter().enumerate() {
+        assert_eq!(
+            solve(example, Engine::RayCasting),
+            solve(example, Engine::Pick),
+            "example {}",
+            i + 1
+        );
+    }
+}
+
+#[test]
+fn engines_agree_on_the_input() {
+    let input = include_str!("../../input.txt");
+    assert_eq!(solve(input, Engine::RayCasting), solve(input, Engine::Pick));
+}
+
+#[test]
+fn render_example_4() {
+    let expected = "\
+··········
+·S──────┐·
+·│┌────┐│·
+·││····││·
+·││····││·
+·│└─┐┌─┘│·
+·│▒▒││▒▒│·
+·└──┘└──┘·
+··········
+";
+    assert_eq!(render(EXAMPLES[3], false), expected);
+}
+
+#[test]
+fn render_keeps_junk_pipes_distinct_without_color() {
+    let rendering = render(EXAMPLES[0], false);
+    assert_eq!(rendering, "-L|F7\n7S─┐|\nL│▒│|\n-└─┘|\nL|-JF\n");
+}
+
+#[test]
+fn render_shades_exactly_the_inside_tiles() {
+    for example in EXAMPLES {
+        let shaded = render(example, true).matches('▒').count();
+        assert_eq!(shaded, solve(example, Engine::RayCasting).1);
+    }
+}
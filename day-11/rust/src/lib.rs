@@ -0,0 +1,192 @@
+/// A picture of the universe before it's expanded. Every row and column with
+/// no galaxies in it will grow by the expansion factor.
+///
+/// Expanding can only grow the universe, so everything that takes a `factor`
+/// panics if it's 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    /// Where each galaxy is as `(y, x)`, in reading order
+    galaxies: Vec<(usize, usize)>,
+    /// How many empty rows come before each row
+    empty_rows_before: Vec<usize>,
+    /// How many empty columns come before each column
+    empty_columns_before: Vec<usize>,
+}
+
+impl Image {
+    pub fn parse(input: &str) -> Self {
+        let galaxies: Vec<(usize, usize)> = input
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.char_indices()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| (y, x))
+            })
+            .collect();
+
+        let height = input.lines().count();
+        let width = input.lines().map(str::len).max().unwrap_or(0);
+
+        Self {
+            empty_rows_before: empty_before(height, galaxies.iter().map(|&(y, _)| y)),
+            empty_columns_before: empty_before(width, galaxies.iter().map(|&(_, x)| x)),
+            galaxies,
+        }
+    }
+
+    /// Galaxy positions as `(y, x)` before expansion
+    pub fn galaxies(&self) -> &[(usize, usize)] {
+        &self.galaxies
+    }
+
+    /// Galaxy positions as `(y, x)` once every empty row and column has been
+    /// replaced by `factor` of them
+    pub fn expanded(&self, factor: usize) -> Vec<(usize, usize)> {
+        self.galaxies
+            .iter()
+            .map(|&galaxy| self.expand(galaxy, factor))
+            .collect()
+    }
+
+    fn expand(&self, (y, x): (usize, usize), factor: usize) -> (usize, usize) {
+        assert!(factor >= 1, "the expansion factor must be at least 1");
+        (
+            y + (factor - 1) * self.empty_rows_before[y],
+            x + (factor - 1) * self.empty_columns_before[x],
+        )
+    }
+
+    /// The distance between the `a`-th and `b`-th galaxies (counting from 0
+    /// in reading order) after expanding by `factor`
+    pub fn distance(&self, a: usize, b: usize, factor: usize) -> Option<usize> {
+        let a = self.expand(*self.galaxies.get(a)?, factor);
+        let b = self.expand(*self.galaxies.get(b)?, factor);
+
+        Some(manhattan_distance(a, b))
+    }
+
+    /// The `k` galaxies closest to the `galaxy`-th one, closest first. Ties go
+    /// to whichever galaxy comes first in reading order.
+    pub fn nearest(&self, galaxy: usize, k: usize, factor: usize) -> Option<Vec<Neighbor>> {
+        let from = self.expand(*self.galaxies.get(galaxy)?, factor);
+
+        let mut neighbors: Vec<Neighbor> = self
+            .expanded(factor)
+            .into_iter()
+            .enumerate()
+            .filter(|&(other, _)| other != galaxy)
+            .map(|(other, to)| Neighbor {
+                galaxy: other,
+                distance: manhattan_distance(from, to),
+            })
+            .collect();
+
+        // Only the closest k need to be sorted
+        let by_distance = |n: &Neighbor| (n.distance, n.galaxy);
+        if k < neighbors.len() {
+            neighbors.select_nth_unstable_by_key(k, by_distance);
+            neighbors.truncate(k);
+        }
+        neighbors.sort_unstable_by_key(by_distance);
+
+        Some(neighbors)
+    }
+
+    /// The two galaxies that are farthest apart after expanding by `factor`.
+    ///
+    /// Rotating by 45° turns Manhattan distance into the larger of the
+    /// distances along the two diagonals, y + x and y - x, so the farthest pair
+    /// is the one furthest apart along either diagonal. That's O(n) instead of
+    /// checking every pair.
+    pub fn farthest_pair(&self, factor: usize) -> Option<Pair> {
+        let expanded = self.expanded(factor);
+        if expanded.len() < 2 {
+            return None;
+        }
+
+        let diagonals: [fn((usize, usize)) -> i128; 2] = [
+            |(y, x)| y as i128 + x as i128,
+            |(y, x)| y as i128 - x as i128,
+        ];
+
+        diagonals
+            .iter()
+            .map(|diagonal| {
+                let along = |&i: &usize| diagonal(expanded[i]);
+                let a = (0..expanded.len()).min_by_key(along).unwrap();
+                let b = (0..expanded.len()).max_by_key(along).unwrap();
+                let (a, b) = (a.min(b), a.max(b));
+
+                Pair {
+                    a,
+                    b,
+                    distance: manhattan_distance(expanded[a], expanded[b]),
+                }
+            })
+            .max_by_key(|pair| pair.distance)
+    }
+
+    /// The sum of the distances between every pair of galaxies after
+    /// expanding by `factor`.
+    ///
+    /// Manhattan distance splits into an x part and a y part so each axis is
+    /// summed on its own from the sorted coordinates in O(n log n).
+    pub fn distance_sum(&self, factor: usize) -> u128 {
+        let (ys, xs): (Vec<usize>, Vec<usize>) = self.expanded(factor).into_iter().unzip();
+        axis_distance_sum(ys) + axis_distance_sum(xs)
+    }
+}
+
+/// Another galaxy and how far away it is
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Neighbor {
+    pub galaxy: usize,
+    pub distance: usize,
+}
+
+/// Two galaxies (with `a < b`) and the distance between them
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Pair {
+    pub a: usize,
+    pub b: usize,
+    pub distance: usize,
+}
+
+/// `counts[i]` is how many of `0..i` never show up in `occupied`
+fn empty_before(len: usize, occupied: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut has_galaxy = vec![false; len];
+    occupied.for_each(|i| has_galaxy[i] = true);
+
+    has_galaxy
+        .iter()
+        .scan(0, |empty, &has_galaxy| {
+            let before = *empty;
+            *empty += usize::from(!has_galaxy);
+            Some(before)
+        })
+        .collect()
+}
+
+/// Once they're sorted the i-th coordinate is at least as far along as the i
+/// before it, so it contributes `i * c` minus the sum of those before it.
+fn axis_distance_sum(mut coordinates: Vec<usize>) -> u128 {
+    coordinates.sort_unstable();
+
+    let mut total = 0;
+    let mut before = 0;
+    for (i, &c) in coordinates.iter().enumerate() {
+        let c = c as u128;
+        total += i as u128 * c - before;
+        before += c;
+    }
+
+    total
+}
+
+pub fn manhattan_distance((a_y, a_x): (usize, usize), (b_y, b_x): (usize, usize)) -> usize {
+    b_y.abs_diff(a_y) + b_x.abs_diff(a_x)
+}
+
+#[cfg(test)]
+mod tests;
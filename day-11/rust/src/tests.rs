@@ -0,0 +1,182 @@
+use super::*;
+
+const EXAMPLE: &str = include_str!("../../test_input.txt");
+
+/// Adds up the distance between every pair of expanded galaxies one at a
+/// time instead of per axis
+fn brute_force(image: &Image, factor: usize) -> u128 {
+    let expanded = image.expanded(factor);
+    let mut total = 0;
+    for (i, &a) in expanded.iter().enumerate() {
+        for &b in &expanded[i + 1..] {
+            total += manhattan_distance(a, b) as u128;
+        }
+    }
+    total
+}
+
+#[test]
+fn example() {
+    let image = Image::parse(EXAMPLE);
+
+    assert_eq!(image.galaxies().len(), 9);
+    assert_eq!(image.distance_sum(2), 374);
+    assert_eq!(image.distance_sum(10), 1030);
+    assert_eq!(image.distance_sum(100), 8410);
+}
+
+#[test]
+fn factor_of_one_leaves_the_image_alone() {
+    let image = Image::parse(EXAMPLE);
+
+    assert_eq!(image.expanded(1), image.galaxies());
+}
+
+#[test]
+fn expanded_coordinates() {
+    let image = Image::parse(EXAMPLE);
+    let expanded = image.expanded(2);
+
+    // Rows 3 and 7 and columns 2, 5 and 8 are empty
+    assert_eq!(expanded[0], (0, 4));
+    assert_eq!(expanded[4], (6, 1));
+    assert_eq!(expanded[8], (11, 5));
+}
+
+#[test]
+fn matches_brute_force() {
+    let input = include_str!("../../input.txt");
+    let image = Image::parse(input);
+
+    for factor in [1, 2, 7, 1_000_000] {
+        assert_eq!(image.distance_sum(factor), brute_force(&image, factor));
+    }
+}
+
+#[test]
+fn many_galaxies() {
+    // A 600 x 600 image with a galaxy in most spots is hundreds of thousands
+    // of galaxies, far too many to check pair by pair
+    let input: String = (0..600)
+        .map(|y| {
+            (0..600)
+                .map(
+                    |x| match (x * 7 + y * 13) % 10 == 0 || x % 50 == 49 || y % 50 == 49 {
+                        true => '.',
+                        false => '#',
+                    },
+                )
+                .chain(['\n'])
+                .collect::<String>()
+        })
+        .collect();
+    let image = Image::parse(&input);
+
+    assert!(image.galaxies().len() > 300_000);
+    assert!(image.distance_sum(1_000_000) > image.distance_sum(2));
+}
+
+#[test]
+fn distance_between_galaxies() {
+    let image = Image::parse(EXAMPLE);
+
+    // Galaxies 5 & 9, 1 & 7, 3 & 6 and 8 & 9 from the puzzle, counting from 1
+    assert_eq!(image.distance(4, 8, 2), Some(9));
+    assert_eq!(image.distance(0, 6, 2), Some(15));
+    assert_eq!(image.distance(2, 5, 2), Some(17));
+    assert_eq!(image.distance(7, 8, 2), Some(5));
+
+    assert_eq!(image.distance(8, 7, 2), Some(5));
+    assert_eq!(image.distance(0, 0, 2), Some(0));
+    assert_eq!(image.distance(0, 9, 2), None);
+}
+
+#[test]
+fn nearest_neighbors() {
+    let image = Image::parse(EXAMPLE);
+    let nearest = image.nearest(7, 3, 2).unwrap();
+
+    assert_eq!(
+        nearest,
+        [
+            Neighbor {
+                galaxy: 8,
+                distance: 5
+            },
+            Neighbor {
+                galaxy: 4,
+                distance: 6
+            },
+            Neighbor {
+                galaxy: 2,
+                distance: 9
+            },
+        ]
+    );
+
+    assert_eq!(image.nearest(0, 100, 2).unwrap().len(), 8);
+    assert_eq!(image.nearest(0, 0, 2), Some(vec![]));
+    assert_eq!(image.nearest(9, 1, 2), None);
+}
+
+#[test]
+fn nearest_neighbors_match_sorting_everything() {
+    let image = Image::parse(include_str!("../../input.txt"));
+
+    for galaxy in [0, 17, 200] {
+        let mut all: Vec<(usize, usize)> = (0..image.galaxies().len())
+            .filter(|&other| other != galaxy)
+            .map(|other| (image.distance(galaxy, other, 10).unwrap(), other))
+            .collect();
+        all.sort();
+
+        let nearest: Vec<(usize, usize)> = image
+            .nearest(galaxy, 25, 10)
+            .unwrap()
+            .iter()
+            .map(|neighbor| (neighbor.distance, neighbor.galaxy))
+            .collect();
+
+        assert_eq!(nearest, all[..25]);
+    }
+}
+
+#[test]
+fn farthest_pair() {
+    let image = Image::parse(EXAMPLE);
+
+    for factor in [1, 2, 10, 1_000_000] {
+        let expanded = image.expanded(factor);
+        let farthest = (0..expanded.len())
+            .flat_map(|a| (a + 1..expanded.len()).map(move |b| (a, b)))
+            .map(|(a, b)| manhattan_distance(expanded[a], expanded[b]))
+            .max();
+
+        assert_eq!(
+            image.farthest_pair(factor).map(|pair| pair.distance),
+            farthest
+        );
+    }
+
+    assert_eq!(
+        image.farthest_pair(2),
+        Some(Pair {
+            a: 1,
+            b: 7,
+            distance: 19
+        })
+    );
+    assert_eq!(Image::parse("#..\n...").farthest_pair(2), None);
+}
+
+#[test]
+#[should_panic(expected = "the expansion factor must be at least 1")]
+fn factor_of_zero() {
+    Image::parse(EXAMPLE).distance_sum(0);
+}
+
+#[test]
+#[should_panic(expected = "the expansion factor must be at least 1")]
+fn factor_of_zero_for_one_galaxy() {
+    Image::parse(EXAMPLE).nearest(0, 3, 0);
+}
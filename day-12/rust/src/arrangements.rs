@@ -0,0 +1,270 @@
+use crate::rng::Rng;
+use crate::{Row, Status};
+
+/// Counts arrangements with a flat DP table where `ways[i][j]` is how many
+/// ways `springs[i..]` can be filled in to match `groups[j..]`. Counts are
+/// `u128`s so heavily unfolded rows still fit, and if even that isn't enough
+/// they saturate rather than wrap so a count is only ever 0 when it really is.
+///
+/// Some states can't be reached from the start of the row and those can
+/// saturate without it mattering. Any state that can be reached has at most
+/// as many arrangements as the whole row, so the row's count is exact unless
+/// it saturated too.
+///
+/// The table and the other scratch space are kept between rows so once
+/// they're big enough for the longest row nothing else gets allocated.
+#[derive(Debug, Default)]
+pub struct Solver {
+    ways: Vec<u128>,
+    /// How many springs in a row starting at each position could be damaged
+    runs: Vec<usize>,
+    /// The width of a row of `ways`, i.e. `groups.len() + 1`
+    stride: usize,
+}
+
+impl Solver {
+    /// `None` if there are more arrangements than fit in a `u128`
+    pub fn count(&mut self, springs: &[Status], groups: &[usize]) -> Option<u128> {
+        self.fill(springs, groups);
+        self.total()
+    }
+
+    /// A uniformly random arrangement, or `None` if there aren't any (or too
+    /// many to count, since then there's nothing to weigh the choices by)
+    pub fn sample(
+        &mut self,
+        springs: &[Status],
+        groups: &[usize],
+        rng: &mut Rng,
+    ) -> Option<Vec<Status>> {
+        self.fill(springs, groups);
+        if self.total()? == 0 {
+            return None;
+        }
+
+        let mut arrangement = Vec::with_capacity(springs.len());
+        let (mut i, mut j) = (0, 0);
+
+        // Pick each branch in proportion to how many arrangements it leads to
+        while i < springs.len() {
+            let operational = match springs[i] {
+                Status::Damaged => 0,
+                _ => self.ways(i + 1, j),
+            };
+            let after_group = self.after_group(springs, groups, i, j);
+            let damaged = after_group.map_or(0, |next| self.ways(next, j + 1));
+
+            if rng.below(operational + damaged) < operational {
+                arrangement.push(Status::Operational);
+                i += 1;
+            } else {
+                let next = after_group.unwrap();
+                push_group(&mut arrangement, groups[j], next - i);
+                (i, j) = (next, j + 1);
+            }
+        }
+
+        Some(arrangement)
+    }
+
+    /// For each spring, the fraction of arrangements in which it's damaged,
+    /// or `None` if there aren't any arrangements (or too many to count)
+    pub fn damage_probabilities(
+        &mut self,
+        springs: &[Status],
+        groups: &[usize],
+    ) -> Option<Vec<f64>> {
+        self.fill(springs, groups);
+        let total = self.total()?;
+        if total == 0 {
+            return None;
+        }
+
+        // The other direction: how many ways there are to get to each state
+        // from the start of the row. Only states that can still be finished
+        // are counted, which keeps every count no bigger than `total`.
+        let n = springs.len();
+        let mut reach = vec![0u128; (n + 1) * self.stride];
+        reach[0] = 1;
+
+        // A group placed at `i` is damaged in that many arrangements, so add
+        // that much to each of its springs using a difference array
+        let mut damaged = vec![0f64; n + 1];
+
+        for i in 0..n {
+            for j in 0..self.stride {
+                let here = reach[i * self.stride + j];
+                if here == 0 || self.ways(i, j) == 0 {
+                    continue;
+                }
+
+                if springs[i] != Status::Damaged && self.ways(i + 1, j) > 0 {
+                    reach[(i + 1) * self.stride + j] += here;
+                }
+
+                let after_group = self
+                    .after_group(springs, groups, i, j)
+                    .filter(|&next| self.ways(next, j + 1) > 0);
+                if let Some(next) = after_group {
+                    reach[next * self.stride + j + 1] += here;
+
+                    let arrangements = here as f64 * self.ways(next, j + 1) as f64;
+                    damaged[i] += arrangements;
+                    damaged[i + groups[j]] -= arrangements;
+                }
+            }
+        }
+
+        let probabilities = damaged[..n]
+            .iter()
+            .scan(0f64, |running, &change| {
+                *running += change;
+                Some(*running / total as f64)
+            })
+            .collect();
+
+        Some(probabilities)
+    }
+
+    /// The whole row's count once the table is filled, unless it saturated
+    fn total(&self) -> Option<u128> {
+        match self.ways(0, 0) {
+            u128::MAX => None,
+            total => Some(total),
+        }
+    }
+
+    fn ways(&self, spring: usize, group: usize) -> u128 {
+        self.ways[spring * self.stride + group]
+    }
+
+    /// If group `j` can start at spring `i` (it has to fit before an
+    /// operational spring and can't run straight into another damaged one),
+    /// where to carry on from: just past the group and the spring that ends it
+    fn after_group(
+        &self,
+        springs: &[Status],
+        groups: &[usize],
+        i: usize,
+        j: usize,
+    ) -> Option<usize> {
+        let &group = groups.get(j)?;
+        if springs[i] == Status::Operational || self.runs[i] < group {
+            return None;
+        }
+
+        match springs.get(i + group) {
+            None => Some(springs.len()),
+            Some(Status::Damaged) => None,
+            Some(_) => Some(i + group + 1),
+        }
+    }
+
+    fn fill(&mut self, springs: &[Status], groups: &[usize]) {
+        let (n, m) = (springs.len(), groups.len());
+        self.stride = m + 1;
+
+        self.ways.clear();
+        self.ways.resize((n + 1) * self.stride, 0);
+        self.runs.clear();
+        self.runs.resize(n + 1, 0);
+
+        for i in (0..n).rev() {
+            if springs[i] != Status::Operational {
+                self.runs[i] = self.runs[i + 1] + 1;
+            }
+        }
+
+        // Nothing left to fill in only works if there are no groups left
+        self.ways[n * self.stride + m] = 1;
+
+        for i in (0..n).rev() {
+            for j in 0..=m {
+                let mut ways = 0u128;
+
+                if springs[i] != Status::Damaged {
+                    ways = self.ways(i + 1, j);
+                }
+
+                // Or start the next group here
+                if let Some(next) = self.after_group(springs, groups, i, j) {
+                    ways = ways.saturating_add(self.ways(next, j + 1));
+                }
+
+                self.ways[i * self.stride + j] = ways;
+            }
+        }
+    }
+}
+
+/// `length` damaged springs followed by the operational one that ends the
+/// group, unless it's at the very end of the row
+fn push_group(arrangement: &mut Vec<Status>, length: usize, taken: usize) {
+    arrangement.extend(std::iter::repeat_n(Status::Damaged, length));
+    if taken > length {
+        arrangement.push(Status::Operational);
+    }
+}
+
+/// Every concrete arrangement of a row with all of its `?`s filled in. The
+/// DP table is used to skip branches that don't lead anywhere so getting from
+/// one arrangement to the next never wanders down a dead end.
+pub(crate) struct Arrangements<'a> {
+    row: &'a Row,
+    solver: Solver,
+    /// Partial arrangements still to be finished, with the state they're in
+    stack: Vec<(usize, usize, Vec<Status>)>,
+}
+
+impl<'a> Arrangements<'a> {
+    pub fn new(row: &'a Row) -> Self {
+        let mut solver = Solver::default();
+        solver.fill(&row.springs, &row.groups);
+        let stack = match solver.ways(0, 0) {
+            0 => vec![],
+            _ => vec![(0, 0, Vec::with_capacity(row.springs.len()))],
+        };
+
+        Self { row, solver, stack }
+    }
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row> {
+        let (springs, groups) = (&self.row.springs, &self.row.groups);
+
+        while let Some((i, j, mut arrangement)) = self.stack.pop() {
+            if i == springs.len() {
+                return Some(Row {
+                    springs: arrangement,
+                    groups: groups.clone(),
+                });
+            }
+
+            let after_group = self
+                .solver
+                .after_group(springs, groups, i, j)
+                .filter(|&next| self.solver.ways(next, j + 1) > 0);
+            let operational = springs[i] != Status::Damaged && self.solver.ways(i + 1, j) > 0;
+
+            // Pushed first so the operational branch is finished first
+            if let Some(next) = after_group {
+                let mut damaged = match operational {
+                    true => arrangement.clone(),
+                    false => std::mem::take(&mut arrangement),
+                };
+                push_group(&mut damaged, groups[j], next - i);
+                self.stack.push((next, j + 1, damaged));
+            }
+
+            if operational {
+                arrangement.push(Status::Operational);
+                self.stack.push((i + 1, j, arrangement));
+            }
+        }
+
+        None
+    }
+}
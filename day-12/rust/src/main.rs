@@ -1,4 +1,11 @@
-use std::collections::HashMap;
+mod arrangements;
+mod rng;
+use arrangements::{Arrangements, Solver};
+use rng::Rng;
+
+/// `--row` lists every arrangement up to this many and takes samples past it
+const LIST_LIMIT: u128 = 20;
+const SAMPLES: usize = 10;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 enum Status {
@@ -48,125 +55,202 @@ impl Row {
         Self { springs, groups }
     }
 
-    fn replace(&self, position: usize, status: Status) -> Self {
-        self.clone().replace_owned(position, status)
+    /// `factor` copies of the row with `separator` between each copy of the
+    /// springs. Part 2 is five copies separated by unknown springs.
+    fn unfold(&self, factor: usize, separator: Status) -> Self {
+        assert!(factor > 0, "a row has to be unfolded at least once");
+
+        let mut springs = Vec::with_capacity(factor * (self.springs.len() + 1));
+        for copy in 0..factor {
+            if copy > 0 {
+                springs.push(separator);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Self {
+            springs,
+            groups: self.groups.repeat(factor),
+        }
     }
 
-    fn replace_owned(mut self, position: usize, status: Status) -> Self {
-        self.springs[position] = status;
-        self
+    fn arrangements(&self) -> Arrangements<'_> {
+        Arrangements::new(self)
     }
+}
 
-    fn unfold(self) -> Self {
-        let springs_length = self.springs.len();
-        let mut springs = self.springs;
-        springs.push(Status::Unknown);
+fn main() {
+    // let input = include_str!("../../test_input.txt");
+    let input = include_str!("../../input.txt");
 
-        let mut springs: Vec<Status> = springs
-            .into_iter()
-            .cycle()
-            .take(5 * (springs_length + 1))
-            .collect();
-        let _ = springs.pop();
-
-        let groups_length = self.groups.len();
-        let groups = self
-            .groups
-            .into_iter()
-            .cycle()
-            .take(5 * groups_length)
-            .collect();
+    let mut threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut explore: Vec<usize> = vec![];
+    let mut unfold = false;
+    let mut factor = 5;
+    let mut separator = Status::Unknown;
+    let mut growth = None;
+    let mut rng = Rng::from_entropy();
 
-        Self { springs, groups }
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threads" => {
+                threads = args
+                    .next()
+                    .expect("--threads needs a number")
+                    .parse()
+                    .unwrap();
+            }
+            "--row" => {
+                let row = args.next().expect("--row needs a number").parse().unwrap();
+                explore.push(row);
+            }
+            "--unfold" => unfold = true,
+            "--factor" => {
+                factor = args
+                    .next()
+                    .expect("--factor needs a number")
+                    .parse()
+                    .unwrap();
+            }
+            "--separator" => {
+                let c = args.next().and_then(|arg| arg.chars().next());
+                separator = Status::parse(c.expect("--separator needs one of . # ?"));
+            }
+            "--growth" => {
+                let k: usize = args
+                    .next()
+                    .expect("--growth needs a number")
+                    .parse()
+                    .unwrap();
+                growth = Some(k);
+            }
+            "--seed" => {
+                let seed = args.next().expect("--seed needs a number").parse().unwrap();
+                rng = Rng::new(seed);
+            }
+            _ => panic!("unknown argument: {arg}"),
+        }
     }
 
-    fn possible_arrangements(mut self, cache: &mut Cache) -> u64 {
-        if let Some(num) = cache.get(&(self.springs.clone(), self.groups.clone())) {
-            return *num;
+    let rows: Vec<_> = input.lines().map(Row::parse).collect();
+
+    for &number in &explore {
+        let row = rows
+            .get(number.wrapping_sub(1))
+            .expect("--row is out of range (rows start at 1)");
+        match unfold {
+            true => explore_row(&row.unfold(factor, separator), &mut rng),
+            false => explore_row(row, &mut rng),
         }
+    }
 
-        match (self.springs.first(), self.groups.first().copied()) {
-            (None, None) => 1,
-            (None, Some(_)) => 0,               // invalid
-            (Some(Status::Damaged), None) => 0, // invalid
-            (Some(Status::Unknown), None) => self.trim_and_continue(cache),
-            (Some(Status::Operational), _) => self.trim_and_continue(cache),
-            (Some(Status::Damaged), Some(mut group_size)) => {
-                let mut slice = self.springs.as_slice();
-
-                loop {
-                    match (slice, group_size) {
-                        ([], 0) => return u64::from(self.groups.len() == 1), // done
-                        ([], _) => return 0,                                 // invalid
-                        ([Status::Damaged, _rest @ ..], 0) => return 0,
-                        ([Status::Damaged, rest @ ..], _) => {
-                            slice = rest;
-                            group_size -= 1;
-                            continue;
-                        }
-                        ([Status::Operational, rest @ ..], 0) => {
-                            let new_springs = Vec::from(rest);
-                            let _ = std::mem::replace(&mut self.springs, new_springs);
-                            self.groups.remove(0);
-                            return self.possible_arrangements(cache);
-                        }
-                        ([Status::Operational, _rest @ ..], _) => return 0, // invalid
-                        ([Status::Unknown, rest @ ..], 0) => {
-                            let new_springs = Vec::from(rest);
-                            let _ = std::mem::replace(&mut self.springs, new_springs);
-                            self.groups.remove(0);
-                            return self.possible_arrangements(cache);
-                        }
-                        ([Status::Unknown, rest @ ..], _) => {
-                            slice = rest;
-                            group_size -= 1;
-                            continue;
-                        }
-                    };
+    let part1 = total_arrangements(&rows, threads).expect("too many arrangements");
+    println!("part1 = {part1}");
+
+    let unfolded: Vec<_> = rows
+        .iter()
+        .map(|row| row.unfold(factor, separator))
+        .collect();
+    let part2 = total_arrangements(&unfolded, threads).expect("too many arrangements");
+    println!("part2 = {part2}");
+
+    if let Some(k) = growth {
+        let mut previous = None;
+        for (factor, total) in (1..=k).zip(growth_by_factor(&rows, k, separator, threads)) {
+            let Some(total) = total else {
+                println!("factor {factor:>2}: more than fit in a u128");
+                break;
+            };
+
+            match previous {
+                Some(previous) => {
+                    let ratio = total as f64 / previous as f64;
+                    println!("factor {factor:>2}: {total} (x{ratio:.3})")
                 }
+                None => println!("factor {factor:>2}: {total}"),
             }
-            (Some(Status::Unknown), Some(_)) => {
-                let new_row = self.replace(0, Status::Operational);
-                let new_springs = new_row.springs.clone();
-                let try_operational = new_row.possible_arrangements(cache);
-                cache.insert((new_springs, self.groups.clone()), try_operational);
-
-                let new_row = self.replace_owned(0, Status::Damaged);
-                let groups = new_row.groups.clone();
-                let new_springs = new_row.springs.clone();
-                let try_damaged = new_row.possible_arrangements(cache);
-                cache.insert((new_springs, groups), try_damaged);
-
-                try_operational + try_damaged
-            }
+            previous = Some(total);
         }
     }
+}
 
-    fn trim_and_continue(mut self, cache: &mut Cache) -> u64 {
-        self.springs.remove(0);
-        self.possible_arrangements(cache)
-    }
+/// The total number of arrangements when unfolding by every factor from 1
+/// up to `k`, as long as they fit in a `u128`
+fn growth_by_factor(
+    rows: &[Row],
+    k: usize,
+    separator: Status,
+    threads: usize,
+) -> Vec<Option<u128>> {
+    (1..=k)
+        .map(|factor| {
+            let unfolded: Vec<Row> = rows
+                .iter()
+                .map(|row| row.unfold(factor, separator))
+                .collect();
+            total_arrangements(&unfolded, threads)
+        })
+        .collect()
 }
 
-type Cache = HashMap<(Vec<Status>, Vec<usize>), u64>;
+/// Shows a row's arrangements (or a sample of them) and how likely each `?`
+/// is to be damaged
+fn explore_row(row: &Row, rng: &mut Rng) {
+    let mut solver = Solver::default();
+    let Some(count) = solver.count(&row.springs, &row.groups) else {
+        println!("{row:?}: more arrangements than fit in a u128\n");
+        return;
+    };
+    println!("{row:?}: {count} arrangements");
 
-fn main() {
-    // let input = include_str!("../../test_input.txt");
-    let input = include_str!("../../input.txt");
+    if count <= LIST_LIMIT {
+        for arrangement in row.arrangements() {
+            println!("  {arrangement:?}");
+        }
+    } else {
+        println!("  {SAMPLES} picked at random:");
+        for _ in 0..SAMPLES {
+            let springs = solver.sample(&row.springs, &row.groups, rng).unwrap();
+            let groups = row.groups.clone();
+            println!("  {:?}", Row { springs, groups });
+        }
+    }
 
-    let rows: Vec<_> = input.lines().map(Row::parse).collect();
+    if let Some(probabilities) = solver.damage_probabilities(&row.springs, &row.groups) {
+        println!("  chance each ? is damaged:");
+        for (i, probability) in probabilities.iter().enumerate() {
+            if row.springs[i] == Status::Unknown {
+                println!("    {i:>3}: {probability:.3}");
+            }
+        }
+    }
+    println!();
+}
 
-    let mut cache = HashMap::<(Vec<Status>, Vec<usize>), u64>::new();
-    let part1: u64 = rows
-        .clone()
-        .into_iter()
-        .map(|row| row.possible_arrangements(&mut cache))
-        .sum();
-    println!("part1 = {part1}");
+/// Splits the rows between threads that each have their own solver. `None`
+/// if the total doesn't fit in a `u128`.
+fn total_arrangements(rows: &[Row], threads: usize) -> Option<u128> {
+    let chunk_size = rows.len().div_ceil(threads.max(1)).max(1);
 
-    let part2: u64 = rows
-        .into_iter()
-        .map(|row| row.unfold().possible_arrangements(&mut cache))
-        .sum();
-    println!("part2 = {part2}");
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = rows
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(|| {
+                    let mut solver = Solver::default();
+                    chunk.iter().try_fold(0u128, |total, row| {
+                        total.checked_add(solver.count(&row.springs, &row.groups)?)
+                    })
+                })
+            })
+            .collect();
+
+        handles.into_iter().try_fold(0u128, |total, handle| {
+            total.checked_add(handle.join().unwrap()?)
+        })
+    })
 }
+
+#[cfg(test)]
+mod tests;
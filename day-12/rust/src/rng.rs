@@ -0,0 +1,44 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// SplitMix64, which is tiny and more than random enough for picking sample
+/// arrangements
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Seeded from the random keys std already generates for `HashMap`
+    pub fn from_entropy() -> Self {
+        Self(RandomState::new().build_hasher().finish())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u128(&mut self) -> u128 {
+        (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64())
+    }
+
+    /// A uniformly random number in `0..bound`. Values from the short last
+    /// stretch of `u128` that would favor small results are thrown away.
+    pub fn below(&mut self, bound: u128) -> u128 {
+        assert!(bound > 0, "can't pick from an empty range");
+        let threshold = bound.wrapping_neg() % bound;
+
+        loop {
+            let value = self.next_u128();
+            if value >= threshold {
+                return value % bound;
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use super::arrangements::Solver;
+use super::rng::Rng;
+use super::{growth_by_factor, total_arrangements, Row, Status};
+use std::collections::HashSet;
+
+const EXAMPLE: &str = include_str!("../../test_input.txt");
+
+fn count(line: &str) -> u128 {
+    let row = Row::parse(line);
+    Solver::default().count(&row.springs, &row.groups).unwrap()
+}
+
+#[test]
+fn example_rows() {
+    let counts: Vec<u128> = EXAMPLE.lines().map(count).collect();
+    assert_eq!(counts, [1, 4, 1, 1, 4, 10]);
+}
+
+#[test]
+fn example_rows_unfolded() {
+    let mut solver = Solver::default();
+    let counts: Vec<u128> = EXAMPLE
+        .lines()
+        .map(|line| {
+            let row = Row::parse(line).unfold(5, Status::Unknown);
+            solver.count(&row.springs, &row.groups).unwrap()
+        })
+        .collect();
+
+    assert_eq!(counts, [1, 16384, 1, 16, 2500, 506250]);
+}
+
+#[test]
+fn edge_cases() {
+    assert_eq!(count("# 1"), 1);
+    assert_eq!(count(". 1"), 0);
+    assert_eq!(count("? 1"), 1);
+    assert_eq!(count("### 2"), 0);
+    assert_eq!(count("##.## 2,2"), 1);
+    assert_eq!(count("##?## 2,2"), 1);
+    assert_eq!(count("##### 2,2"), 0);
+    assert_eq!(count("??? 1,1"), 1);
+    assert_eq!(count("????? 1,1"), 6);
+}
+
+#[test]
+fn threads_do_not_change_the_answer() {
+    let rows: Vec<Row> = include_str!("../../input.txt")
+        .lines()
+        .map(Row::parse)
+        .collect();
+
+    let one = total_arrangements(&rows, 1);
+    for threads in [2, 3, 8, 10_000] {
+        assert_eq!(total_arrangements(&rows, threads), one);
+    }
+    assert_eq!(one, Some(7286));
+}
+
+/// Whether `arrangement` is one of `row`'s with every `?` filled in
+fn is_arrangement_of(arrangement: &Row, row: &Row) -> bool {
+    let groups: Vec<usize> = arrangement
+        .springs
+        .split(|&status| status != Status::Damaged)
+        .map(<[Status]>::len)
+        .filter(|&len| len > 0)
+        .collect();
+
+    arrangement.springs.len() == row.springs.len()
+        && groups == row.groups
+        && arrangement
+            .springs
+            .iter()
+            .zip(&row.springs)
+            .all(|(&a, &b)| b == Status::Unknown || a == b)
+}
+
+#[test]
+fn enumerates_every_arrangement_once() {
+    for line in EXAMPLE
+        .lines()
+        .chain(["????.??#???#?.??? 1,1,1,3,1", ". 1"])
+    {
+        let row = Row::parse(line);
+        let arrangements: Vec<Row> = row.arrangements().collect();
+
+        assert_eq!(arrangements.len() as u128, count(line), "{line}");
+        assert!(arrangements.iter().all(|a| is_arrangement_of(a, &row)));
+
+        let unique: HashSet<Vec<Status>> = arrangements.into_iter().map(|a| a.springs).collect();
+        assert_eq!(unique.len() as u128, count(line));
+    }
+}
+
+#[test]
+fn enumerates_lazily() {
+    // Far too many to collect
+    let row = Row::parse("???????????????????????????????????????????????????????? 1,1,1");
+    let first: Vec<Row> = row.arrangements().take(3).collect();
+
+    assert_eq!(first.len(), 3);
+    assert!(first.iter().all(|a| is_arrangement_of(a, &row)));
+}
+
+#[test]
+fn samples_are_valid_and_cover_every_arrangement() {
+    let row = Row::parse("?###???????? 3,2,1");
+    let mut solver = Solver::default();
+    let mut rng = Rng::new(2023);
+
+    let mut seen = HashSet::new();
+    for _ in 0..1000 {
+        let springs = solver.sample(&row.springs, &row.groups, &mut rng).unwrap();
+        let arrangement = Row {
+            springs,
+            groups: row.groups.clone(),
+        };
+        assert!(is_arrangement_of(&arrangement, &row));
+        seen.insert(arrangement.springs);
+    }
+
+    assert_eq!(seen.len(), 10);
+    assert_eq!(solver.sample(&[Status::Operational], &[1], &mut rng), None);
+}
+
+#[test]
+fn damage_probabilities_match_enumeration() {
+    for line in EXAMPLE.lines().chain(["????.??#???#?.??? 1,1,1,3,1"]) {
+        let row = Row::parse(line);
+        let arrangements: Vec<Row> = row.arrangements().collect();
+
+        let expected: Vec<f64> = (0..row.springs.len())
+            .map(|i| {
+                let damaged = arrangements
+                    .iter()
+                    .filter(|a| a.springs[i] == Status::Damaged)
+                    .count();
+                damaged as f64 / arrangements.len() as f64
+            })
+            .collect();
+
+        let probabilities = Solver::default()
+            .damage_probabilities(&row.springs, &row.groups)
+            .unwrap();
+
+        for (p, e) in probabilities.iter().zip(&expected) {
+            assert!(
+                (p - e).abs() < 1e-9,
+                "{line}: {probabilities:?} vs {expected:?}"
+            );
+        }
+    }
+
+    assert_eq!(
+        Solver::default().damage_probabilities(&[Status::Operational], &[1]),
+        None
+    );
+}
+
+#[test]
+fn unfold_with_any_factor_and_separator() {
+    let row = Row::parse("???.### 1,1,3");
+
+    assert_eq!(
+        format!("{:?}", row.unfold(1, Status::Unknown)),
+        "???.### [1, 1, 3]"
+    );
+    assert_eq!(
+        format!("{:?}", row.unfold(3, Status::Operational)),
+        "???.###.???.###.???.### [1, 1, 3, 1, 1, 3, 1, 1, 3]"
+    );
+    assert_eq!(
+        format!("{:?}", row.unfold(2, Status::Damaged)),
+        "???.####???.### [1, 1, 3, 1, 1, 3]"
+    );
+}
+
+#[test]
+fn operational_separators_make_copies_independent() {
+    // Each copy can be filled in on its own so the count is just a power
+    let mut solver = Solver::default();
+    for line in EXAMPLE.lines() {
+        let once = count(line);
+        for factor in [2, 5, 12, 20] {
+            let row = Row::parse(line).unfold(factor, Status::Operational);
+            assert_eq!(
+                solver.count(&row.springs, &row.groups),
+                Some(once.pow(factor as u32)),
+                "{line} x{factor}"
+            );
+        }
+    }
+}
+
+#[test]
+fn growth_matches_enumeration_for_small_factors() {
+    let rows: Vec<Row> = EXAMPLE.lines().map(Row::parse).collect();
+    let growth = growth_by_factor(&rows, 3, Status::Unknown, 2);
+
+    for (factor, &total) in (1..=3).zip(&growth) {
+        let enumerated: usize = rows
+            .iter()
+            .map(|row| row.unfold(factor, Status::Unknown).arrangements().count())
+            .sum();
+        assert_eq!(total, Some(enumerated as u128));
+    }
+    assert_eq!(growth[0], Some(21));
+}
+
+#[test]
+fn factors_well_beyond_five() {
+    let rows: Vec<Row> = EXAMPLE.lines().map(Row::parse).collect();
+    let growth = growth_by_factor(&rows, 12, Status::Unknown, 2);
+
+    assert_eq!(growth[4], Some(525152));
+    assert!(growth.windows(2).all(|pair| pair[1] > pair[0]));
+}
+
+#[test]
+fn too_many_to_count() {
+    // 60 damaged springs somewhere in 3,059 is way more than 2^128 ways
+    let row = Row::parse("?????????????????????????????????????????????????? 1")
+        .unfold(60, Status::Unknown);
+    let mut solver = Solver::default();
+
+    assert_eq!(solver.count(&row.springs, &row.groups), None);
+    assert_eq!(solver.damage_probabilities(&row.springs, &row.groups), None);
+    assert_eq!(
+        solver.sample(&row.springs, &row.groups, &mut Rng::new(1)),
+        None
+    );
+
+    // Still enumerable since saturating never turns a count into 0
+    let first = row.arrangements().next().unwrap();
+    assert!(is_arrangement_of(&first, &row));
+}
+
+#[test]
+fn damage_probabilities_for_a_huge_row_that_still_fits() {
+    // Lots of ways to fill in a prefix never get finished, and counting all
+    // of them used to overflow even though the row's total fits
+    let groups: Vec<String> = std::iter::repeat_n(1, 35)
+        .chain([150])
+        .map(|group| group.to_string())
+        .collect();
+    let row = Row::parse(&format!("{} {}", "?".repeat(300), groups.join(",")));
+    let mut solver = Solver::default();
+
+    assert_eq!(
+        solver.count(&row.springs, &row.groups),
+        Some(1274485500897474426204025926117)
+    );
+
+    let probabilities = solver
+        .damage_probabilities(&row.springs, &row.groups)
+        .unwrap();
+    assert!(probabilities.iter().all(|p| (0.0..=1.0).contains(p)));
+
+    // Damaged springs in an arrangement always add up to the groups' total
+    let expected: usize = row.groups.iter().sum();
+    assert!((probabilities.iter().sum::<f64>() - expected as f64).abs() < 1e-6);
+}
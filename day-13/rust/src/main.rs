@@ -56,120 +56,41 @@ impl Pattern {
         columns
     }
 
-    fn vertical_reflection(&self) -> Option<usize> {
-        let columns = self.columns();
-
-        for column in 1..self.width() {
-            let mut left = column - 1;
-            let mut right = column;
-
-            while columns[right] == columns[left] {
-                if left == 0 || right == self.width() - 1 {
-                    return Some(column);
-                } else {
-                    left -= 1;
-                    right += 1;
-                }
-            }
-        }
-
-        None
-    }
-
-    fn horizontal_reflection(&self) -> Option<usize> {
-        for row in 1..self.grid.len() {
-            let mut upper = row - 1;
-            let mut lower = row;
-
-            while self.grid[lower] == self.grid[upper] {
-                if upper == 0 || lower == self.grid.len() - 1 {
-                    return Some(row);
-                } else {
-                    upper -= 1;
-                    lower += 1;
-                }
-            }
-        }
-
-        None
+    /// The number of columns to the left of a vertical line of reflection
+    /// with exactly `smudges` tiles that don't match their mirror image
+    fn vertical_reflection(&self, smudges: usize) -> Option<usize> {
+        reflection(&self.columns(), smudges)
     }
 
-    fn summarize(&self) -> usize {
-        self.vertical_reflection()
-            .unwrap_or_else(|| self.horizontal_reflection().unwrap() * 100)
+    /// The number of rows above a horizontal line of reflection with exactly
+    /// `smudges` tiles that don't match their mirror image
+    fn horizontal_reflection(&self, smudges: usize) -> Option<usize> {
+        reflection(&self.grid, smudges)
     }
 
-    fn vertical_reflection_smudge(&self) -> Option<usize> {
-        let columns = self.columns();
-        let old_vertical_reflection = self.vertical_reflection();
-
-        for column in 1..self.width() {
-            if old_vertical_reflection == Some(column) {
-                continue;
-            }
-
-            let mut left = column - 1;
-            let mut right = column;
-            let mut smudge_fixed = false;
-
-            loop {
-                if columns[right] != columns[left] {
-                    if !smudge_fixed && one_off(&columns[right], &columns[left]) {
-                        smudge_fixed = true;
-                    } else {
-                        break;
-                    }
-                }
-
-                if left == 0 || right == self.width() - 1 {
-                    return Some(column);
-                } else {
-                    left -= 1;
-                    right += 1;
-                }
-            }
-        }
-
-        None
+    fn summarize(&self, smudges: usize) -> Option<usize> {
+        self.vertical_reflection(smudges)
+            .or_else(|| Some(self.horizontal_reflection(smudges)? * 100))
     }
+}
 
-    fn horizontal_reflection_smudge(&self) -> Option<usize> {
-        let old_horizontal_reflection = self.horizontal_reflection();
-
-        for row in 1..self.grid.len() {
-            if old_horizontal_reflection == Some(row) {
-                continue;
-            }
-
-            let mut upper = row - 1;
-            let mut lower = row;
-            let mut smudge_fixed = false;
-
-            loop {
-                if self.grid[lower] != self.grid[upper] {
-                    if !smudge_fixed && one_off(&self.grid[lower], &self.grid[upper]) {
-                        smudge_fixed = true;
-                    } else {
-                        break;
-                    }
-                }
-
-                if upper == 0 || lower == self.grid.len() - 1 {
-                    return Some(row);
-                } else {
-                    upper -= 1;
-                    lower += 1;
-                }
-            }
-        }
-
-        None
-    }
+/// Finds the first line between `lines[i - 1]` and `lines[i]` where folding
+/// the lines over each other leaves exactly `smudges` mismatched tiles.
+/// Lines that run off the edge don't have to match anything.
+fn reflection(lines: &[Vec<Tile>], smudges: usize) -> Option<usize> {
+    (1..lines.len()).find(|&line| {
+        let (before, after) = lines.split_at(line);
+        let mismatches = before.iter().rev().zip(after).try_fold(0, |total, (a, b)| {
+            let total = total + differences(a, b);
+            (total <= smudges).then_some(total)
+        });
+
+        mismatches == Some(smudges)
+    })
+}
 
-    fn summarize_smudge(&self) -> usize {
-        self.vertical_reflection_smudge()
-            .unwrap_or_else(|| self.horizontal_reflection_smudge().unwrap() * 100)
-    }
+fn differences(a: &[Tile], b: &[Tile]) -> usize {
+    a.iter().zip(b).filter(|(a, b)| a != b).count()
 }
 
 fn main() {
@@ -178,13 +99,39 @@ fn main() {
 
     let patterns: Vec<_> = input.split("\n\n").map(Pattern::parse).collect();
 
-    let part1: usize = patterns.iter().map(|pattern| pattern.summarize()).sum();
-    println!("part1 = {part1}");
-
-    let part2: usize = patterns.iter().map(Pattern::summarize_smudge).sum();
-    println!("part2 = {part2}");
+    println!("part1 = {}", total(&patterns, 0).expect("no reflection"));
+    println!(
+        "part2 = {}",
+        total(&patterns, 1).expect("no reflection with a smudge")
+    );
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--smudges" => {
+                let k: usize = args
+                    .next()
+                    .expect("--smudges needs a number")
+                    .parse()
+                    .unwrap();
+                match total(&patterns, k) {
+                    Some(total) => println!("{k} smudges = {total}"),
+                    None => println!("{k} smudges = not every pattern has a reflection"),
+                }
+            }
+            _ => panic!("unknown argument: {arg}"),
+        }
+    }
 }
 
-fn one_off(a: &[Tile], b: &[Tile]) -> bool {
-    a.iter().zip(b.iter()).filter(|(a, b)| a != b).count() == 1
+/// `None` unless every pattern has a reflection with exactly `smudges`
+/// smudges
+fn total(patterns: &[Pattern], smudges: usize) -> Option<usize> {
+    patterns
+        .iter()
+        .map(|pattern| pattern.summarize(smudges))
+        .sum()
 }
+
+#[cfg(test)]
+mod tests;
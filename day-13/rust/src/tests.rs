@@ -0,0 +1,47 @@
+use super::{total, Pattern};
+
+const EXAMPLE: &str = include_str!("../../test_input.txt");
+
+fn patterns() -> Vec<Pattern> {
+    EXAMPLE.split("\n\n").map(Pattern::parse).collect()
+}
+
+#[test]
+fn example_without_smudges() {
+    let patterns = patterns();
+
+    assert_eq!(patterns[0].vertical_reflection(0), Some(5));
+    assert_eq!(patterns[1].horizontal_reflection(0), Some(4));
+    assert_eq!(total(&patterns, 0), Some(405));
+}
+
+#[test]
+fn example_with_one_smudge() {
+    let patterns = patterns();
+
+    assert_eq!(patterns[0].horizontal_reflection(1), Some(3));
+    assert_eq!(patterns[1].horizontal_reflection(1), Some(1));
+    assert_eq!(total(&patterns, 1), Some(400));
+}
+
+#[test]
+fn exactly_k_smudges() {
+    // Every fold of these rows has a different number of mismatches
+    let pattern = Pattern::parse("..##\n...#\n....\n####");
+
+    assert_eq!(pattern.horizontal_reflection(0), None);
+    assert_eq!(pattern.horizontal_reflection(1), Some(1));
+    assert_eq!(pattern.horizontal_reflection(2), None);
+    assert_eq!(pattern.horizontal_reflection(3), Some(2));
+    assert_eq!(pattern.horizontal_reflection(4), Some(3));
+    assert_eq!(pattern.horizontal_reflection(5), None);
+}
+
+#[test]
+fn the_whole_reflection_counts() {
+    // Two rows off by one each adds up to two smudges, not one
+    let pattern = Pattern::parse("###.\n....\n#.#.\n#.#.\n...#\n##..");
+
+    assert_eq!(pattern.horizontal_reflection(1), None);
+    assert_eq!(pattern.horizontal_reflection(2), Some(3));
+}
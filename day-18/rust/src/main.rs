@@ -117,16 +117,13 @@ fn solve<const PART_TWO: bool>(instructions: Vec<Instruction<PART_TWO>>) {
         perimeter += instruction.magnitude;
     }
 
-    let mut area = 0;
-    for points in vertices.windows(2) {
-        let [a, b] = points else { panic!() };
-
-        // Shoelace?
-        area += (a.y + b.y) * (a.x - b.x);
-        // area += (a.x * b.y) - (b.x * a.y);
-    }
-
-    // Pick's Theorem?
-    let part2 = area as u64 / 2 + perimeter as u64 / 2 + 1;
+    let vertices: Vec<(i64, i64)> = vertices
+        .iter()
+        .map(|point| (point.x as i64, point.y as i64))
+        .collect();
+
+    // Shoelace for the area then Pick's theorem for the points in & on the loop
+    let part2 = polygon::lattice_points(&vertices);
+    debug_assert_eq!(polygon::boundary_points(&vertices), perimeter as u64);
     println!("part2 = {part2}");
 }
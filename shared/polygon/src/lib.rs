@@ -0,0 +1,55 @@
+//! Area and lattice point counts for simple polygons whose vertices all sit on
+//! integer coordinates. Days 10 and 18 both boil down to this.
+//!
+//! Vertices are `(x, y)` pairs given in order around the polygon (either way
+//! round) and the last one connects back to the first. Repeating the first
+//! vertex at the end is fine too.
+
+/// Twice the polygon's area, using the shoelace formula. Doubling it keeps
+/// everything in integers.
+pub fn double_area(vertices: &[(i64, i64)]) -> u64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum::<i64>()
+        .unsigned_abs()
+}
+
+/// How many lattice points lie on the polygon's edges. An edge from a to b
+/// passes through gcd(|dx|, |dy|) of them, not counting a itself.
+pub fn boundary_points(vertices: &[(i64, i64)]) -> u64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| gcd((x2 - x1).unsigned_abs(), (y2 - y1).unsigned_abs()))
+        .sum()
+}
+
+/// How many lattice points lie strictly inside the polygon.
+///
+/// Pick's theorem says A = I + B/2 - 1, so I = (2A - B + 2) / 2.
+pub fn interior_points(vertices: &[(i64, i64)]) -> u64 {
+    match double_area(vertices) {
+        0 => 0,
+        double_area => (double_area + 2).saturating_sub(boundary_points(vertices)) / 2,
+    }
+}
+
+/// How many lattice points lie inside the polygon or on its edges
+pub fn lattice_points(vertices: &[(i64, i64)]) -> u64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+fn edges(vertices: &[(i64, i64)]) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+#[cfg(test)]
+mod tests;
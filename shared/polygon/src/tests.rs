@@ -0,0 +1,80 @@
+use super::*;
+
+#[test]
+fn square() {
+    let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+
+    assert_eq!(double_area(&square), 32);
+    assert_eq!(boundary_points(&square), 16);
+    assert_eq!(interior_points(&square), 9);
+    assert_eq!(lattice_points(&square), 25);
+}
+
+#[test]
+fn direction_and_closing_vertex_do_not_matter() {
+    let clockwise = [(0, 0), (0, 3), (5, 3), (5, 0)];
+    let counterclockwise = [(0, 0), (5, 0), (5, 3), (0, 3), (0, 0)];
+
+    assert_eq!(double_area(&clockwise), double_area(&counterclockwise));
+    assert_eq!(
+        interior_points(&clockwise),
+        interior_points(&counterclockwise)
+    );
+    assert_eq!(interior_points(&clockwise), 8);
+}
+
+#[test]
+fn diagonal_edges() {
+    // Right triangle with legs of 4: (3 * 3 - 3) / 2 points strictly inside
+    let triangle = [(0, 0), (4, 0), (0, 4)];
+
+    assert_eq!(double_area(&triangle), 16);
+    assert_eq!(boundary_points(&triangle), 12);
+    assert_eq!(interior_points(&triangle), 3);
+}
+
+#[test]
+fn collinear_vertices_are_harmless() {
+    let corners = [(0, 0), (3, 0), (3, 2), (0, 2)];
+    let every_point = [
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (3, 1),
+        (3, 2),
+        (2, 2),
+        (1, 2),
+        (0, 2),
+        (0, 1),
+    ];
+
+    assert_eq!(interior_points(&corners), interior_points(&every_point));
+    assert_eq!(lattice_points(&corners), 12);
+}
+
+#[test]
+fn concave() {
+    // A U shape: 3x3 block with the middle of the top row notched out
+    let u = [
+        (0, 0),
+        (3, 0),
+        (3, 3),
+        (2, 3),
+        (2, 1),
+        (1, 1),
+        (1, 3),
+        (0, 3),
+    ];
+
+    assert_eq!(double_area(&u), 14);
+    assert_eq!(interior_points(&u), 0);
+    assert_eq!(lattice_points(&u), 16);
+}
+
+#[test]
+fn degenerate() {
+    assert_eq!(double_area(&[]), 0);
+    assert_eq!(interior_points(&[]), 0);
+    assert_eq!(interior_points(&[(0, 0), (5, 0)]), 0);
+}